@@ -0,0 +1,223 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{Result, WrapErr};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::input::INPUT_DIR;
+
+/// The answers accepted for each day's real input, stored as
+///
+/// ```toml
+/// [day_1]
+/// part_1 = "70369"
+/// part_2 = "203002"
+/// wrong_part_2 = ["203001"]
+/// ```
+///
+/// along with the answers that were rejected when submitting them
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<String, DayAnswers>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_2: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    wrong_part_1: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    wrong_part_2: Vec<String>,
+}
+
+impl DayAnswers {
+    fn part_mut(&mut self, part: u8) -> (&mut Option<String>, &mut Vec<String>) {
+        if part == 1 {
+            (&mut self.part_1, &mut self.wrong_part_1)
+        } else {
+            (&mut self.part_2, &mut self.wrong_part_2)
+        }
+    }
+}
+
+const HEADER: &str = "# Answers accepted for the real inputs in this directory, checked by `cargo run --release -- verify`";
+
+/// The outcome of checking an answer against the recorded one
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+pub fn default_answers_path() -> PathBuf {
+    Path::new(INPUT_DIR).join("answers.toml")
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Loads the answers file, treating a missing file as having no answers recorded
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+        Answers::parse(&content).wrap_err_with(|| format!("Could not parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_toml())
+            .wrap_err_with(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Serializes the answers with the days in calendar order, keeping multi-line answers readable
+    pub fn to_toml(&self) -> String {
+        let mut out = format!("{HEADER}\n");
+        let days = self
+            .days
+            .iter()
+            .sorted_by_key(|(day, _)| day.trim_start_matches("day_").parse::<u8>().ok());
+        for (day, answers) in days {
+            out.push_str(&format!("\n[{day}]\n"));
+            for (key, answer) in [("part_1", &answers.part_1), ("part_2", &answers.part_2)] {
+                if let Some(answer) = answer {
+                    out.push_str(&format!("{key} = {}\n", toml_string(answer)));
+                }
+            }
+            for (key, wrong) in [
+                ("wrong_part_1", &answers.wrong_part_1),
+                ("wrong_part_2", &answers.wrong_part_2),
+            ] {
+                if !wrong.is_empty() {
+                    let wrong = wrong.iter().map(|w| toml_string(w)).join(", ");
+                    out.push_str(&format!("{key} = [{wrong}]\n"));
+                }
+            }
+        }
+        out
+    }
+
+    pub fn record_correct(&mut self, day: u8, part: u8, answer: &str) {
+        let day_answers = self.days.entry(format!("day_{day}")).or_default();
+        *day_answers.part_mut(part).0 = Some(answer.to_string());
+    }
+
+    pub fn record_wrong(&mut self, day: u8, part: u8, answer: &str) {
+        let day_answers = self.days.entry(format!("day_{day}")).or_default();
+        let wrong = day_answers.part_mut(part).1;
+        if !wrong.iter().any(|w| w == answer) {
+            wrong.push(answer.to_string());
+        }
+    }
+
+    pub fn is_known_wrong(&self, day: u8, part: u8, answer: &str) -> bool {
+        self.days
+            .get(&format!("day_{day}"))
+            .map(|answers| match part {
+                1 => &answers.wrong_part_1,
+                _ => &answers.wrong_part_2,
+            })
+            .is_some_and(|wrong| wrong.iter().any(|w| w.trim_end() == answer.trim_end()))
+    }
+
+    pub fn expected(&self, day: u8, part: u8) -> Option<&str> {
+        let answers = self.days.get(&format!("day_{day}"))?;
+        match part {
+            1 => answers.part_1.as_deref(),
+            2 => answers.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Trailing whitespace is ignored so multi-line answers can be stored as TOML multi-line strings
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.expected(day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected.trim_end() == answer.trim_end() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+/// Multi-line answers (like day 10's screen) are written as multi-line strings
+fn toml_string(s: &str) -> String {
+    if s.contains('\n') && !s.contains("\"\"\"") && !s.contains('\\') {
+        format!("\"\"\"\n{}\n\"\"\"", s.trim_end_matches('\n'))
+    } else {
+        toml::Value::String(s.to_string()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{default_answers_path, Answers, Verdict};
+
+    const ANSWERS: &str = r#"
+[day_1]
+part_1 = "24000"
+
+[day_10]
+part_2 = """
+##..
+..##
+"""
+"#;
+
+    #[test]
+    fn check_test() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(Some("24000"), answers.expected(1, 1));
+        assert_eq!(Verdict::Pass, answers.check(1, 1, "24000"));
+        assert_eq!(
+            Verdict::Fail {
+                expected: "24000".to_string()
+            },
+            answers.check(1, 1, "45000")
+        );
+        assert_eq!(Verdict::Missing, answers.check(1, 2, "45000"));
+        assert_eq!(Verdict::Missing, answers.check(2, 1, "15"));
+        assert_eq!(Verdict::Pass, answers.check(10, 2, "##..\n..##"));
+    }
+
+    #[test]
+    fn record_test() {
+        let mut answers = Answers::parse(ANSWERS).unwrap();
+        answers.record_wrong(2, 1, "12");
+        answers.record_wrong(2, 1, "12");
+        answers.record_correct(2, 1, "15");
+        assert!(answers.is_known_wrong(2, 1, "12"));
+        assert!(!answers.is_known_wrong(2, 2, "12"));
+        assert_eq!(Verdict::Pass, answers.check(2, 1, "15"));
+
+        let saved = answers.to_toml();
+        assert!(saved.find("[day_2]").unwrap() < saved.find("[day_10]").unwrap());
+        assert!(saved.contains("wrong_part_1 = [\"12\"]"));
+        assert_eq!(answers, Answers::parse(&saved).unwrap());
+    }
+
+    #[test]
+    fn recorded_answers_test() {
+        let answers = Answers::load(&default_answers_path()).unwrap();
+        assert_eq!(Some("70369"), answers.expected(1, 1));
+        assert!(
+            Answers::load(&default_answers_path().with_extension("missing"))
+                .unwrap()
+                .expected(1, 1)
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::solution::DynSolution;
+
+/// A timed step of running a day
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Parse,
+    PartOne,
+    PartTwo,
+}
+
+impl Phase {
+    fn for_part(part: u8) -> Self {
+        if part == 1 {
+            Phase::PartOne
+        } else {
+            Phase::PartTwo
+        }
+    }
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::PartOne => write!(f, "part 1"),
+            Phase::PartTwo => write!(f, "part 2"),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut samples = samples
+            .iter()
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .collect::<Vec<_>>();
+        samples.sort_by(f64::total_cmp);
+
+        let middle = samples.len() / 2;
+        let median_ms = if samples.len() % 2 == 0 {
+            (samples.get(middle.checked_sub(1)?)? + samples[middle]) / 2.0
+        } else {
+            samples[middle]
+        };
+
+        Some(Stats {
+            min_ms: *samples.first()?,
+            median_ms,
+            max_ms: *samples.last()?,
+        })
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct PhaseBenchmark {
+    pub phase: Phase,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DayBenchmark {
+    pub day: u8,
+    pub runs: usize,
+    pub phases: Vec<PhaseBenchmark>,
+    /// Set when a phase failed, in which case only the runs before the failure are included
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Runs parsing and the given parts of a day `runs` times, timing each phase separately
+pub fn bench_day(day: &dyn DynSolution, input: &str, parts: &[u8], runs: usize) -> DayBenchmark {
+    let mut samples: Vec<(Phase, Vec<Duration>)> = Vec::new();
+    let mut record =
+        |phase: Phase, elapsed: Duration| match samples.iter_mut().find(|(p, _)| *p == phase) {
+            Some((_, phase_samples)) => phase_samples.push(elapsed),
+            None => samples.push((phase, vec![elapsed])),
+        };
+
+    let mut error = None;
+    'runs: for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = match day.parse(input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                error = Some(format!("Could not parse input: {e:#}"));
+                break;
+            }
+        };
+        record(Phase::Parse, start.elapsed());
+
+        for part in parts {
+            let start = Instant::now();
+            if let Err(e) = day.part(*part, &parsed) {
+                error = Some(format!("Part {part} failed: {e:#}"));
+                break 'runs;
+            }
+            record(Phase::for_part(*part), start.elapsed());
+        }
+    }
+
+    DayBenchmark {
+        day: day.day(),
+        runs,
+        phases: samples
+            .into_iter()
+            .filter_map(|(phase, phase_samples)| {
+                Stats::from_samples(&phase_samples).map(|stats| PhaseBenchmark { phase, stats })
+            })
+            .collect(),
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{bench_day, Phase, Stats};
+    use crate::day_1::Day1;
+
+    #[test]
+    fn stats_test() {
+        let stats = Stats::from_samples(&[
+            Duration::from_millis(4),
+            Duration::from_millis(1),
+            Duration::from_millis(2),
+        ])
+        .unwrap();
+        assert_eq!(
+            Stats {
+                min_ms: 1.0,
+                median_ms: 2.0,
+                max_ms: 4.0
+            },
+            stats
+        );
+
+        let stats = Stats::from_samples(&[Duration::from_millis(1), Duration::from_millis(2)]);
+        assert_eq!(1.5, stats.unwrap().median_ms);
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn bench_day_test() {
+        let benchmark = bench_day(&Day1, "1000\n2000\n\n4000\n\n", &[1, 2], 3);
+        assert_eq!(None, benchmark.error);
+        assert_eq!(
+            vec![Phase::Parse, Phase::PartOne, Phase::PartTwo],
+            benchmark.phases.iter().map(|p| p.phase).collect::<Vec<_>>()
+        );
+
+        let benchmark = bench_day(&Day1, "1000\nnot a number\n\n", &[1, 2], 3);
+        assert!(benchmark.error.is_some());
+        assert!(benchmark.phases.is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+use advent_of_code_2022::{
+    day_1::{
+        elf_calories,
+        report::{to_csv, to_json, ElfRow, Report},
+        top_k_from_reader,
+    },
+    input::{load_input, open_input, InputSource},
+};
+use color_eyre::eyre::{eyre, Result};
+
+const USAGE: &str = "Usage: day_1 [PATH]
+       day_1 --top <K> [PATH]
+       day_1 --report [--buckets <N>] [--csv | --json] [PATH]
+
+Without options both parts of day 1 are solved. --top streams the input (which can be larger
+than memory) and prints the K elves carrying the most calories. --report prints statistics
+of the elves' inventories, or exports one row per elf as CSV or JSON.
+PATH defaults to `src/input/day_1.txt`, `-` reads from stdin.";
+
+enum Mode {
+    Solve,
+    Top(usize),
+    Report { buckets: usize, export: Export },
+}
+
+enum Export {
+    None,
+    Csv,
+    Json,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let (mode, path) = parse_args(&args).map_err(|e| eyre!("{e}\n\n{USAGE}"))?;
+    let source = InputSource::from_arg(path);
+
+    match mode {
+        Mode::Solve => advent_of_code_2022::run_day(1, path),
+        Mode::Top(k) => {
+            for (rank, elf) in top_k_from_reader(open_input(1, &source)?, k)?
+                .iter()
+                .enumerate()
+            {
+                println!("{:>3}. elf {:>6}: {}", rank + 1, elf.elf, elf.calories);
+            }
+            Ok(())
+        }
+        Mode::Report { buckets, export } => {
+            let inventories = elf_calories(&load_input(1, &source)?)?;
+            let rows = || inventories.iter().map(ElfRow::from).collect::<Vec<_>>();
+            match export {
+                Export::None => println!("{}", Report::new(&inventories, buckets)?),
+                Export::Csv => print!("{}", to_csv(&rows())),
+                Export::Json => println!("{}", to_json(&rows())?),
+            }
+            Ok(())
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(Mode, Option<&str>)> {
+    let mut mode = Mode::Solve;
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), &mut mode) {
+            ("--top", Mode::Solve) => {
+                mode = Mode::Top(
+                    args.next()
+                        .ok_or(eyre!("--top requires a value"))?
+                        .parse()?,
+                )
+            }
+            ("--report", Mode::Solve) => {
+                mode = Mode::Report {
+                    buckets: 10,
+                    export: Export::None,
+                }
+            }
+            ("--buckets", Mode::Report { buckets, .. }) => {
+                *buckets = args
+                    .next()
+                    .ok_or(eyre!("--buckets requires a value"))?
+                    .parse()?
+            }
+            ("--csv", Mode::Report { export, .. }) => *export = Export::Csv,
+            ("--json", Mode::Report { export, .. }) => *export = Export::Json,
+            ("--top" | "--report", _) => return Err(eyre!("--top and --report can't be combined")),
+            ("--buckets" | "--csv" | "--json", _) => {
+                return Err(eyre!("{arg} can only be used with --report"))
+            }
+            (other, _) if path.is_none() => path = Some(other),
+            (other, _) => return Err(eyre!("Unrecognized argument '{other}'")),
+        }
+    }
+    Ok((mode, path))
+}
@@ -0,0 +1,6 @@
+fn main() -> color_eyre::eyre::Result<()> {
+    color_eyre::install()?;
+
+    let input = std::env::args().nth(1);
+    advent_of_code_2022::run_day(10, input.as_deref())
+}
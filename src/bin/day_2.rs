@@ -0,0 +1,148 @@
+use advent_of_code_2022::{
+    day_2::{
+        optimizer::{
+            best_score, mapping_scores, simulate, OpponentModel, PatternOpponent, RandomOpponent,
+        },
+        parse_guide,
+        rules::{Rules, Strategy},
+    },
+    input::{load_input, InputSource},
+};
+use color_eyre::eyre::{eyre, Result};
+
+const USAGE: &str = "Usage: day_2 [PATH]
+       day_2 --optimize [PATH]
+       day_2 --simulate <random|PATTERN> [--games <N>] [--seed <S>] [--outcome] [PATH]
+
+Without options both parts of day 2 are solved. --optimize prints the best possible score
+against the guide's opponent and the guide's score under every mapping of X/Y/Z to moves.
+--simulate plays the guide's second column (as moves, or as outcomes with --outcome) N times
+(default 1000) against random moves, or a repeated PATTERN of opponent symbols like `A,C,C`.
+PATH defaults to `src/input/day_2.txt`, `-` reads from stdin.";
+
+enum Mode {
+    Solve,
+    Optimize,
+    Simulate {
+        opponent: String,
+        games: usize,
+        seed: u64,
+        strategy: Strategy,
+    },
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let (mode, path) = parse_args(&args).map_err(|e| eyre!("{e}\n\n{USAGE}"))?;
+    if let Mode::Solve = mode {
+        return advent_of_code_2022::run_day(2, path);
+    }
+
+    let rules = Rules::rock_paper_scissors();
+    let guide = parse_guide(&load_input(2, &InputSource::from_arg(path))?)?;
+    match mode {
+        Mode::Solve => unreachable!("solved above"),
+        Mode::Optimize => {
+            println!("Best possible score: {}", best_score(&rules, &guide)?);
+            println!();
+            println!("Score  | Mapping");
+            for mapping in mapping_scores(&rules, &guide)? {
+                let moves = rules
+                    .player_symbols()
+                    .iter()
+                    .zip(&mapping.moves)
+                    .map(|(symbol, played)| format!("{symbol}={played:<8}"))
+                    .collect::<Vec<_>>();
+                println!("{:>6} | {}", mapping.score, moves.join(" ").trim_end());
+            }
+        }
+        Mode::Simulate {
+            opponent,
+            games,
+            seed,
+            strategy,
+        } => {
+            let mut opponent: Box<dyn OpponentModel> = if opponent == "random" {
+                Box::new(RandomOpponent::new(seed))
+            } else {
+                Box::new(PatternOpponent::parse(&rules, &opponent)?)
+            };
+            let simulation = simulate(&rules, &guide, strategy, opponent.as_mut(), games)?;
+            let percent = |n: usize| 100.0 * n as f64 / simulation.rounds().max(1) as f64;
+            println!("Games  | {:>10}", simulation.games);
+            println!(
+                "Wins   | {:>10} ({:.1}%)",
+                simulation.wins,
+                percent(simulation.wins)
+            );
+            println!(
+                "Draws  | {:>10} ({:.1}%)",
+                simulation.draws,
+                percent(simulation.draws)
+            );
+            println!(
+                "Losses | {:>10} ({:.1}%)",
+                simulation.losses,
+                percent(simulation.losses)
+            );
+            println!(
+                "Score  | {:>10.1} (min {}, max {})",
+                simulation.mean_score(),
+                simulation.min_score,
+                simulation.max_score
+            );
+        }
+    }
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<(Mode, Option<&str>)> {
+    let mut mode = Mode::Solve;
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), &mut mode) {
+            ("--optimize", Mode::Solve) => mode = Mode::Optimize,
+            ("--simulate", Mode::Solve) => {
+                mode = Mode::Simulate {
+                    opponent: args
+                        .next()
+                        .ok_or(eyre!("--simulate requires an opponent"))?
+                        .clone(),
+                    games: 1000,
+                    seed: 2022,
+                    strategy: Strategy::Move,
+                }
+            }
+            ("--games", Mode::Simulate { games, .. }) => {
+                *games = args
+                    .next()
+                    .ok_or(eyre!("--games requires a value"))?
+                    .parse()?
+            }
+            ("--seed", Mode::Simulate { seed, .. }) => {
+                *seed = args
+                    .next()
+                    .ok_or(eyre!("--seed requires a value"))?
+                    .parse()?
+            }
+            ("--outcome", Mode::Simulate { strategy, .. }) => *strategy = Strategy::Outcome,
+            ("--optimize" | "--simulate", _) => {
+                return Err(eyre!("--optimize and --simulate can't be combined"))
+            }
+            ("--games" | "--seed" | "--outcome", _) => {
+                return Err(eyre!("{arg} can only be used with --simulate"))
+            }
+            (other, _) if path.is_none() => path = Some(other),
+            (other, _) => return Err(eyre!("Unrecognized argument '{other}'")),
+        }
+    }
+    Ok((mode, path))
+}
@@ -0,0 +1,97 @@
+use advent_of_code_2022::{
+    day_3::{
+        badges::{assign_badges, Assignment},
+        parse_rucksacks, GROUP_SIZE,
+    },
+    input::{load_input, InputSource},
+};
+use color_eyre::eyre::{eyre, Result};
+
+const USAGE: &str = "Usage: day_3 [PATH]
+       day_3 --discover [--size <N>] [--limit <K>] [PATH]
+
+Without options both parts of day 3 are solved. --discover ignores the order of the rucksacks
+and finds up to K (default 10) ways of splitting them into groups of N (default 3) that each
+share exactly one badge item, or explains why there are none.
+PATH defaults to `src/input/day_3.txt`, `-` reads from stdin.";
+
+enum Mode {
+    Solve,
+    Discover { size: usize, limit: usize },
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let (mode, path) = parse_args(&args).map_err(|e| eyre!("{e}\n\n{USAGE}"))?;
+    let Mode::Discover { size, limit } = mode else {
+        return advent_of_code_2022::run_day(3, path);
+    };
+
+    let rucksacks = parse_rucksacks(&load_input(3, &InputSource::from_arg(path))?)?;
+    match assign_badges(&rucksacks, size, limit)? {
+        Assignment::Found {
+            partitions,
+            exhaustive,
+        } => {
+            for (i, partition) in partitions.iter().enumerate() {
+                println!("Partition {}:", i + 1);
+                for group in &partition.groups {
+                    let lines = group
+                        .members
+                        .iter()
+                        .map(|m| rucksacks[*m].line.to_string())
+                        .collect::<Vec<_>>();
+                    println!("  lines {}: badge {}", lines.join(", "), group.badge);
+                }
+            }
+            if exhaustive {
+                println!("{} partitions in total", partitions.len());
+            } else {
+                println!("Stopped after {limit} partitions, there are more");
+            }
+            Ok(())
+        }
+        Assignment::Impossible(conflict) => Err(eyre!("No valid grouping: {conflict}")),
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(Mode, Option<&str>)> {
+    let mut mode = Mode::Solve;
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), &mut mode) {
+            ("--discover", Mode::Solve) => {
+                mode = Mode::Discover {
+                    size: GROUP_SIZE,
+                    limit: 10,
+                }
+            }
+            ("--size", Mode::Discover { size, .. }) => {
+                *size = args
+                    .next()
+                    .ok_or(eyre!("--size requires a value"))?
+                    .parse()?
+            }
+            ("--limit", Mode::Discover { limit, .. }) => {
+                *limit = args
+                    .next()
+                    .ok_or(eyre!("--limit requires a value"))?
+                    .parse()?
+            }
+            ("--size" | "--limit", _) => {
+                return Err(eyre!("{arg} can only be used with --discover"))
+            }
+            (other, _) if path.is_none() => path = Some(other),
+            (other, _) => return Err(eyre!("Unrecognized argument '{other}'")),
+        }
+    }
+    Ok((mode, path))
+}
@@ -0,0 +1,121 @@
+use advent_of_code_2022::{
+    day_4::{parse_pairs, rota::Assignments},
+    input::{load_input, InputSource},
+};
+use color_eyre::eyre::{eyre, Result};
+
+const USAGE: &str = "Usage: day_4 [PATH]
+       day_4 --covering <SECTION> [PATH]
+       day_4 --overlapping [PATH]
+       day_4 --uncovered [PATH]
+       day_4 --crowded <K> [PATH]
+       day_4 --max-overlap [PATH]
+       day_4 --rota [PATH]
+
+Without options both parts of day 4 are solved. The other options look at the assignments of
+all elves at once, numbered from 1 in input order with two elves per line:
+  --covering     the elves cleaning SECTION
+  --overlapping  every two elves sharing a section, in the same pair or not
+  --uncovered    sections between the first and the last assigned that nobody cleans
+  --crowded      sections cleaned by more than K elves
+  --max-overlap  the most elves cleaning one section, and where
+  --rota         who cleans each run of sections
+PATH defaults to `src/input/day_4.txt`, `-` reads from stdin.";
+
+enum Mode {
+    Solve,
+    Covering(u64),
+    Overlapping,
+    Uncovered,
+    Crowded(usize),
+    MaxOverlap,
+    Rota,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let (mode, path) = parse_args(&args).map_err(|e| eyre!("{e}\n\n{USAGE}"))?;
+    if let Mode::Solve = mode {
+        return advent_of_code_2022::run_day(4, path);
+    }
+
+    let pairs = parse_pairs(&load_input(4, &InputSource::from_arg(path))?)?;
+    let assignments = Assignments::new(&pairs);
+    match mode {
+        Mode::Solve => unreachable!("solved above"),
+        Mode::Covering(section) => {
+            let elves = assignments.covering(section);
+            println!("{} elves clean section {section}", elves.len());
+            for elf in elves {
+                let sections = assignments.elf(elf).expect("covering elves exist");
+                println!("  elf {elf}: {sections}");
+            }
+        }
+        Mode::Overlapping => {
+            let pairs = assignments.overlapping_pairs();
+            for (first, second) in &pairs {
+                println!("elves {first} and {second}");
+            }
+            println!("{} overlapping pairs", pairs.len());
+        }
+        Mode::Uncovered => {
+            let uncovered = assignments.uncovered();
+            if uncovered.is_empty() {
+                println!("Every section from the first to the last is cleaned");
+            } else {
+                println!("Nobody cleans: {uncovered}");
+            }
+        }
+        Mode::Crowded(limit) => println!(
+            "More than {limit} elves clean: {}",
+            assignments.crowded(limit)
+        ),
+        Mode::MaxOverlap => {
+            let (most, sections) = assignments.max_overlap();
+            println!("{most} elves clean: {sections}");
+        }
+        Mode::Rota => println!("{}", assignments.rota()),
+    }
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<(Mode, Option<&str>)> {
+    let mut mode = Mode::Solve;
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let query = match arg.as_str() {
+            "--covering" => Mode::Covering(
+                args.next()
+                    .ok_or(eyre!("--covering requires a section"))?
+                    .parse()?,
+            ),
+            "--overlapping" => Mode::Overlapping,
+            "--uncovered" => Mode::Uncovered,
+            "--crowded" => Mode::Crowded(
+                args.next()
+                    .ok_or(eyre!("--crowded requires a number of elves"))?
+                    .parse()?,
+            ),
+            "--max-overlap" => Mode::MaxOverlap,
+            "--rota" => Mode::Rota,
+            other if path.is_none() => {
+                path = Some(other);
+                continue;
+            }
+            other => return Err(eyre!("Unrecognized argument '{other}'")),
+        };
+        if !matches!(mode, Mode::Solve) {
+            return Err(eyre!("Only one query can be run at a time"));
+        }
+        mode = query;
+    }
+    Ok((mode, path))
+}
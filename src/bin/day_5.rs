@@ -0,0 +1,155 @@
+use advent_of_code_2022::{
+    day_5::{
+        crane::{crate_mover, MODELS},
+        parse_procedure,
+        planner::{plan, Planning},
+        replay::Replay,
+        top_crates_of_stacks,
+    },
+    input::{load_input, InputSource},
+};
+use color_eyre::eyre::{eyre, Result};
+
+const USAGE: &str = "Usage: day_5 [PATH]
+       day_5 [--crane <MODEL>] [--replay | --validate] [PATH]
+       day_5 [--crane <MODEL>] --plan <MESSAGE> [--max-states <N>] [PATH]
+
+Without options both parts of day 5 are solved. --crane runs the procedure with another model
+of crane and prints the crates ending up on top. MODEL is one of:
+  9000        moves crates one at a time (part 1, the default)
+  9001        moves all the crates at once (part 2)
+  limited:N   moves up to N crates at once, making several trips for bigger moves
+  rotating:N  moves all the crates at once, putting the top N at the bottom
+Names can also be written the way they are printed, like `CrateMover 9001`.
+--replay draws the stacks after every instruction. --validate checks that every instruction
+can be carried out, reporting the first one that can't with the stacks at that point.
+--plan ignores the instructions and finds as few as possible that leave MESSAGE on top of the
+stacks, looking at up to N (default 1000000) arrangements of the crates. The plan is printed
+as puzzle input.
+PATH defaults to `src/input/day_5.txt`, `-` reads from stdin.";
+
+struct Options<'a> {
+    crane: Option<&'a str>,
+    replay: bool,
+    validate: bool,
+    plan: Option<&'a str>,
+    max_states: usize,
+    path: Option<&'a str>,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let options = parse_args(&args).map_err(|e| eyre!("{e}\n\n{USAGE}"))?;
+    if options.crane.is_none() && !options.replay && !options.validate && options.plan.is_none() {
+        return advent_of_code_2022::run_day(5, options.path);
+    }
+
+    let crane = crate_mover(options.crane.unwrap_or("9000"))?;
+    let procedure = parse_procedure(&load_input(5, &InputSource::from_arg(options.path))?)?;
+    if let Some(message) = options.plan {
+        return match plan(
+            &procedure.stacks,
+            message,
+            crane.as_ref(),
+            options.max_states,
+        ) {
+            Planning::Found(procedure) => {
+                println!("{procedure}");
+                Ok(())
+            }
+            Planning::Impossible => Err(eyre!(
+                "No procedure leaves {message:?} on top with the {}",
+                crane.name()
+            )),
+            Planning::GaveUp { states } => Err(eyre!(
+                "Gave up after looking at {states} arrangements, try a higher --max-states"
+            )),
+        };
+    }
+    if options.validate {
+        procedure.validate(crane.as_ref())?;
+        println!(
+            "All {} instructions can be carried out by the {}",
+            procedure.instructions.len(),
+            crane.name()
+        );
+        return Ok(());
+    }
+    if options.replay {
+        let mut replay = Replay::new(&procedure, crane.as_ref());
+        println!("Starting stacks:\n{}\n", replay.stacks());
+        while replay.step()? {
+            let instruction = replay
+                .previous_instruction()
+                .expect("an instruction was just carried out");
+            println!(
+                "{}. {instruction}:\n{}\n",
+                replay.position(),
+                replay.stacks()
+            );
+        }
+    }
+    println!(
+        "{}: {}",
+        crane.name(),
+        top_crates_of_stacks(&procedure, crane.as_ref())?
+    );
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Options<'_>> {
+    let mut options = Options {
+        crane: None,
+        replay: false,
+        validate: false,
+        plan: None,
+        max_states: 1_000_000,
+        path: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--crane" if options.crane.is_none() => {
+                let model = args.next().ok_or_else(|| {
+                    eyre!("--crane requires a model, one of {}", MODELS.join(", "))
+                })?;
+                options.crane = Some(model.as_str());
+            }
+            "--crane" => return Err(eyre!("Only one crane can be used at a time")),
+            "--replay" => options.replay = true,
+            "--validate" => options.validate = true,
+            "--plan" => {
+                let message = args
+                    .next()
+                    .ok_or_else(|| eyre!("--plan requires a message"))?;
+                options.plan = Some(message.as_str());
+            }
+            "--max-states" => {
+                options.max_states = args
+                    .next()
+                    .ok_or_else(|| eyre!("--max-states requires a value"))?
+                    .parse()?
+            }
+            other if options.path.is_none() => options.path = Some(other),
+            other => return Err(eyre!("Unrecognized argument '{other}'")),
+        }
+    }
+    if [options.replay, options.validate, options.plan.is_some()]
+        .iter()
+        .filter(|o| **o)
+        .count()
+        > 1
+    {
+        return Err(eyre!(
+            "Only one of --replay, --validate and --plan can be used"
+        ));
+    }
+    Ok(options)
+}
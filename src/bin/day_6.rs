@@ -0,0 +1,121 @@
+use std::io::Write;
+
+use advent_of_code_2022::{
+    day_6::{
+        markers,
+        stream::{Decoder, Event},
+        Marker, Unit,
+    },
+    input::{load_input, open_input, InputSource},
+};
+use color_eyre::eyre::{eyre, Result};
+
+const USAGE: &str = "Usage: day_6 [PATH]
+       day_6 --size <N> [--bytes] [--all] [PATH]
+       day_6 --stream [--size <N>] [PATH]
+
+Without options both parts of day 6 are solved. --size looks for markers of N different
+characters instead, printing where the first one ends, or every one with --all. --bytes counts
+bytes instead of characters.
+--stream decodes the signal as it is read, splitting it into packets after each marker of N
+(default 4) different bytes, so it works on endless pipes too. Marker bytes aren't reused.
+PATH defaults to `src/input/day_6.txt`, `-` reads from stdin.";
+
+struct Options<'a> {
+    size: Option<usize>,
+    unit: Unit,
+    all: bool,
+    stream: bool,
+    path: Option<&'a str>,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let options = parse_args(&args).map_err(|e| eyre!("{e}\n\n{USAGE}"))?;
+    if options.stream {
+        let size = options.size.unwrap_or(Marker::PacketMarker.size());
+        return stream(&InputSource::from_arg(options.path), size);
+    }
+    let Some(size) = options.size else {
+        return advent_of_code_2022::run_day(6, options.path);
+    };
+
+    let signal = load_input(6, &InputSource::from_arg(options.path))?;
+    let mut found = markers(signal.trim_end(), size, options.unit);
+    if options.all {
+        for position in found {
+            println!("{position}");
+        }
+    } else {
+        let position = found
+            .next()
+            .ok_or_else(|| eyre!("No marker of {size} different symbols"))?;
+        println!("{position}");
+    }
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Options<'_>> {
+    let mut options = Options {
+        size: None,
+        unit: Unit::Chars,
+        all: false,
+        stream: false,
+        path: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                options.size = Some(
+                    args.next()
+                        .ok_or_else(|| eyre!("--size requires a value"))?
+                        .parse()?,
+                )
+            }
+            "--bytes" => options.unit = Unit::Bytes,
+            "--all" => options.all = true,
+            "--stream" => options.stream = true,
+            other if options.path.is_none() => options.path = Some(other),
+            other => return Err(eyre!("Unrecognized argument '{other}'")),
+        }
+    }
+    if options.stream && (options.all || options.unit == Unit::Bytes) {
+        return Err(eyre!("--stream always reads bytes and finds every marker"));
+    }
+    if options.size.is_none() && (options.all || options.unit == Unit::Bytes) {
+        return Err(eyre!("--bytes and --all can only be used with --size"));
+    }
+    Ok(options)
+}
+
+/// Prints the events of the stream as they come
+fn stream(source: &InputSource, size: usize) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    for event in Decoder::new(open_input(6, source)?, size) {
+        match event? {
+            Event::Skipped { start, bytes } => {
+                writeln!(out, "{start}: skipped {} bytes", bytes.len())?
+            }
+            Event::Marker { end, window } => writeln!(
+                out,
+                "{end}: marker {}",
+                String::from_utf8_lossy(&window).escape_debug()
+            )?,
+            Event::Payload { start, bytes } => writeln!(
+                out,
+                "{start}: payload {}",
+                String::from_utf8_lossy(&bytes).escape_debug()
+            )?,
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::io::BufRead;
+
+use advent_of_code_2022::{
+    day_7::{shell::Shell, size_of_smallest_dir_to_delete, total_size_of_at_most},
+    input::{open_input, InputSource},
+};
+use color_eyre::eyre::{eyre, Result};
+
+const USAGE: &str = "Usage: day_7 [PATH]
+       day_7 --shell [PATH]
+
+Without options both parts of day 7 are solved. --shell runs the transcript on an empty disk
+one line at a time. Besides `cd` and `ls` it understands `mkdir PATH`, `touch PATH [SIZE]`,
+`rm [-r] PATH`, `mv FROM TO` and `du [PATH]`, printing what they output and the used space and
+both answers again after each of them.
+PATH defaults to `src/input/day_7.txt`, `-` reads from stdin, so more commands can be piped
+after the puzzle input.";
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let (shell, path) = parse_args(&args).map_err(|e| eyre!("{e}\n\n{USAGE}"))?;
+    if !shell {
+        return advent_of_code_2022::run_day(7, path);
+    }
+
+    let mut shell = Shell::default();
+    for (number, line) in open_input(7, &InputSource::from_arg(path))?
+        .lines()
+        .enumerate()
+    {
+        let line = line?;
+        let output = shell
+            .execute(&line)
+            .map_err(|e| eyre!("line {}: {e}", number + 1))?;
+        let command = line
+            .strip_prefix('$')
+            .and_then(|c| c.split_whitespace().next());
+        if matches!(command, None | Some("cd") | Some("ls")) {
+            continue;
+        }
+        println!("{}{line}", shell.current_dir());
+        for output_line in output {
+            println!("{output_line}");
+        }
+        report(&shell);
+    }
+    println!("after the transcript:");
+    report(&shell);
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<(bool, Option<&str>)> {
+    let mut shell = false;
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            "--shell" => shell = true,
+            other if path.is_none() => path = Some(other),
+            other => return Err(eyre!("Unrecognized argument '{other}'")),
+        }
+    }
+    Ok((shell, path))
+}
+
+/// Prints the used space and the answers to both parts for the disk as it is now
+fn report(shell: &Shell) {
+    let system = shell.system();
+    let part_two = match size_of_smallest_dir_to_delete(system) {
+        Ok(size) => size.to_string(),
+        Err(e) => e.to_string(),
+    };
+    println!(
+        "  used {}, part 1: {}, part 2: {part_two}",
+        system.root_size(),
+        total_size_of_at_most(100_000, system)
+    );
+}
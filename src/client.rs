@@ -0,0 +1,296 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde::Deserialize;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+
+/// Environment variable holding the session cookie of a logged in account
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding the server, e.g. to talk to a local stand-in
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Optional `aoc.toml` next to `Cargo.toml`, the environment variables take precedence over it
+#[derive(Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    session: Option<String>,
+    base_url: Option<String>,
+}
+
+pub fn default_config_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("aoc.toml")
+}
+
+impl Config {
+    /// A missing file is an empty config
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.is_file() {
+            return Ok(Config::default());
+        }
+        let config = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+        toml::from_str(&config).wrap_err_with(|| format!("Could not parse {}", path.display()))
+    }
+}
+
+/// What the server said about a submitted answer
+#[derive(Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect(String),
+    /// Submitted too soon after a wrong answer, the message says how long to wait
+    TooSoon(String),
+    /// The part was already solved, so the answer was not checked
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl SubmitOutcome {
+    /// Classifies the page returned after submitting by the message in its `<article>`
+    pub fn from_response(page: &str) -> Self {
+        let message = article_text(page);
+        if message.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if message.contains("That's not the right answer") {
+            SubmitOutcome::Incorrect(message)
+        } else if message.contains("You gave an answer too recently") {
+            SubmitOutcome::TooSoon(message)
+        } else if message.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::AlreadySolved
+        } else {
+            SubmitOutcome::Unknown(message)
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Configured from the environment variables, falling back to `config`
+    pub fn from_env(config: Config) -> Result<Self> {
+        let session = std::env::var(SESSION_VAR)
+            .ok()
+            .or(config.session)
+            .ok_or(eyre!(
+                "No session token, set {SESSION_VAR} or `session` in {}",
+                default_config_path().display()
+            ))?;
+        let base_url = std::env::var(BASE_URL_VAR)
+            .ok()
+            .or(config.base_url)
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(Client::new(&base_url, &session))
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String> {
+        let url = self.day_url(day, "/input");
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| request_error(&url, e))?;
+        response
+            .into_string()
+            .wrap_err_with(|| format!("Could not read the response from {url}"))
+    }
+
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<SubmitOutcome> {
+        let url = self.day_url(day, "/answer");
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| request_error(&url, e))?;
+        let page = response
+            .into_string()
+            .wrap_err_with(|| format!("Could not read the response from {url}"))?;
+        Ok(SubmitOutcome::from_response(&page))
+    }
+
+    fn day_url(&self, day: u8, path: &str) -> String {
+        format!("{}/{YEAR}/day/{day}{path}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn request_error(url: &str, error: ureq::Error) -> color_eyre::Report {
+    match error {
+        ureq::Error::Status(status, response) => {
+            let body = response.into_string().unwrap_or_default();
+            eyre!("{url} returned {status}: {}", body.trim())
+        }
+        ureq::Error::Transport(e) => eyre!("Request to {url} failed: {e}"),
+    }
+}
+
+/// The text of the page's `<article>` (or the whole page without one), without any markup
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| {
+            article.split_once('>').map_or("", |a| a.1)
+        });
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    use super::{Client, Config, SubmitOutcome};
+
+    /// Answers each incoming request with the next of `responses`, returning the requests it got
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body_in = vec![0; content_length];
+                reader.read_exact(&mut body_in).unwrap();
+                request.push_str(&String::from_utf8(body_in).unwrap());
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetch_input_test() {
+        let (base_url, server) = serve(vec![(200, "1000\n2000\n"), (400, "Please log in")]);
+        let client = Client::new(&base_url, "abc123\n");
+
+        assert_eq!("1000\n2000\n", client.fetch_input(1).unwrap());
+        let error = client.fetch_input(2).unwrap_err().to_string();
+        assert!(error.contains("400: Please log in"), "{error}");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("\r\nCookie: session=abc123\r\n"));
+        assert!(requests[1].starts_with("GET /2022/day/2/input"));
+    }
+
+    #[test]
+    fn submit_test() {
+        let (base_url, server) = serve(vec![
+            (
+                200,
+                "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+            ),
+            (
+                200,
+                "<article><p>That's not the right answer; your answer is too low.</p></article>",
+            ),
+        ]);
+        let client = Client::new(&format!("{base_url}/"), "abc123");
+
+        assert_eq!(SubmitOutcome::Correct, client.submit(3, 1, "157").unwrap());
+        assert_eq!(
+            SubmitOutcome::Incorrect(
+                "That's not the right answer; your answer is too low.".to_string()
+            ),
+            client.submit(3, 2, "12").unwrap()
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/3/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=1&answer=157"));
+        assert!(requests[1].ends_with("level=2&answer=12"));
+    }
+
+    #[test]
+    fn outcome_test() {
+        assert_eq!(
+            SubmitOutcome::AlreadySolved,
+            SubmitOutcome::from_response(
+                "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"
+            )
+        );
+        assert!(matches!(
+            SubmitOutcome::from_response("<article><p>You gave an answer too recently; you have 30s left to wait.</p></article>"),
+            SubmitOutcome::TooSoon(message) if message.ends_with("30s left to wait.")
+        ));
+        assert_eq!(
+            SubmitOutcome::Unknown("Something else".to_string()),
+            SubmitOutcome::from_response("Something <b>else</b>")
+        );
+    }
+
+    #[test]
+    fn config_test() {
+        let path = std::env::temp_dir().join(format!(
+            "advent_of_code_2022_{}_config_test.toml",
+            std::process::id()
+        ));
+        assert_eq!(Config::default(), Config::load(&path).unwrap());
+
+        std::fs::write(
+            &path,
+            "session = \"abc\"\nbase_url = \"http://localhost:8080\"\n",
+        )
+        .unwrap();
+        let config = Config::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(Some("abc"), config.session.as_deref());
+        assert_eq!(Some("http://localhost:8080"), config.base_url.as_deref());
+    }
+}
@@ -1,54 +1,210 @@
-use std::num::ParseIntError;
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
 
-fn main() {
-    println!("Day 1");
-    let input = include_str!("input/day_1.txt");
+use color_eyre::eyre::{eyre, Result, WrapErr};
 
-    let mut elf_calories = elf_calories(input).unwrap();
-    elf_calories.sort();
-    elf_calories.reverse();
+use crate::solution::Solution;
 
-    let mut total = 0;
-    for (i, val) in elf_calories.iter().take(3).enumerate() {
-        println!("Top {}: {}", i + 1, val);
-        total += val;
+pub mod report;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<ElfTotal>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        elf_totals(input.as_bytes()).collect()
+    }
+
+    fn part_one(elf_totals: &Self::Input) -> Result<Self::PartOne> {
+        top_k(elf_totals.iter().copied(), 1)
+            .first()
+            .map(|elf| elf.calories)
+            .ok_or(eyre!("No elves found in input"))
+    }
+
+    fn part_two(elf_totals: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(top_k(elf_totals.iter().copied(), 3)
+            .iter()
+            .map(|elf| elf.calories)
+            .sum())
+    }
+}
+
+/// Calories carried by a single elf, with the elves numbered from 0 in input order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElfTotal {
+    pub elf: usize,
+    pub calories: u64,
+}
+
+/// Calorie counts of the items carried by a single elf
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfInventory {
+    pub elf: usize,
+    pub items: Vec<u64>,
+    pub calories: u64,
+}
+
+impl ElfInventory {
+    pub fn total(&self) -> ElfTotal {
+        ElfTotal {
+            elf: self.elf,
+            calories: self.calories,
+        }
+    }
+}
+
+/// Every elf's inventory, keeping the individual items
+pub fn elf_calories(input: &str) -> Result<Vec<ElfInventory>> {
+    elf_inventories(input.as_bytes()).collect()
+}
+
+/// The totals of each elf's inventory, read one line at a time so inputs don't have to fit in
+/// memory. Only the running total is kept, however many items an elf carries. Inventories are
+/// separated by one or more blank lines, the last one doesn't need to be
+pub fn elf_totals<R: BufRead>(reader: R) -> ElfTotals<R> {
+    ElfTotals(InventoryReader::new(reader))
+}
+
+/// Like [`elf_totals`], with the items of each elf
+pub fn elf_inventories<R: BufRead>(reader: R) -> ElfInventories<R> {
+    ElfInventories(InventoryReader::new(reader))
+}
+
+pub struct ElfTotals<R>(InventoryReader<R>);
+
+impl<R: BufRead> Iterator for ElfTotals<R> {
+    type Item = Result<ElfTotal>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_elf(|_| {})
+    }
+}
+
+pub struct ElfInventories<R>(InventoryReader<R>);
+
+impl<R: BufRead> Iterator for ElfInventories<R> {
+    type Item = Result<ElfInventory>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut items = Vec::new();
+        let total = self.0.next_elf(|item| items.push(item))?;
+        Some(total.map(|total| ElfInventory {
+            elf: total.elf,
+            items,
+            calories: total.calories,
+        }))
+    }
+}
+
+/// Reads inventories line by line for [`ElfTotals`] and [`ElfInventories`]
+struct InventoryReader<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    next_elf: usize,
+}
+
+impl<R: BufRead> InventoryReader<R> {
+    fn new(reader: R) -> Self {
+        InventoryReader {
+            reader,
+            line: String::new(),
+            line_number: 0,
+            next_elf: 0,
+        }
+    }
+
+    /// Reads the next elf's inventory, handing each item to `on_item` as it goes
+    fn next_elf(&mut self, mut on_item: impl FnMut(u64)) -> Option<Result<ElfTotal>> {
+        let mut items = 0;
+        let mut calories = 0u64;
+        loop {
+            self.line.clear();
+            let read = match self.reader.read_line(&mut self.line) {
+                Ok(read) => read,
+                Err(e) => return Some(Err(e).wrap_err("Could not read inventory")),
+            };
+            self.line_number += 1;
+
+            let item = self.line.trim_end_matches(['\n', '\r']);
+            if read == 0 || item.is_empty() {
+                if items > 0 {
+                    let elf = self.next_elf;
+                    self.next_elf += 1;
+                    return Some(Ok(ElfTotal { elf, calories }));
+                } else if read == 0 {
+                    return None;
+                }
+                continue;
+            }
+
+            let item = match item.parse::<u64>() {
+                Ok(item) => item,
+                Err(e) => {
+                    return Some(Err(eyre!(
+                        "line {}: invalid calories {item:?}: {e}",
+                        self.line_number
+                    )))
+                }
+            };
+            calories = match calories.checked_add(item) {
+                Some(total) => total,
+                None => {
+                    return Some(Err(eyre!(
+                        "line {}: calories of elf {} overflow",
+                        self.line_number,
+                        self.next_elf
+                    )))
+                }
+            };
+            on_item(item);
+            items += 1;
+        }
     }
-    println!("Total top 3: {}", total);
 }
-// fn max_calories_elf(input: &str) -> Result<i32, ParseIntError> {
-//     let mut max = 0;
-//     let mut current = 0;
-//     for line in input.lines() {
-//         if line == "" {
-//             if current > max {
-//                 max = current;
-//             }
-//             current = 0;
-//         } else {
-//             current += line.parse::<i32>()?;
-//         }
-//     }
-//
-//     Ok(max)
-// }
-//
-fn elf_calories(input: &str) -> Result<Vec<u32>, ParseIntError> {
-    let mut result = Vec::new();
-    let mut current = 0;
-    for line in input.lines() {
-        if line.is_empty() {
-            result.push(current);
-            current = 0;
-        } else {
-            current += line.parse::<u32>()?;
+
+/// The `k` elves carrying the most calories, most first, keeping only `k` of them in memory.
+/// On ties the earlier elf ranks higher
+pub fn top_k(elves: impl IntoIterator<Item = ElfTotal>, k: usize) -> Vec<ElfTotal> {
+    // min-heap of the best so far, so the worst of them is the one popped
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for elf in elves {
+        heap.push(Reverse((elf.calories, Reverse(elf.elf))));
+        if heap.len() > k {
+            heap.pop();
         }
     }
-    Ok(result)
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((calories, Reverse(elf)))| ElfTotal { elf, calories })
+        .collect()
 }
 
-#[test]
-fn example_test() {
-    let input = "1000
+/// [`top_k`] straight from a reader, failing on the first invalid inventory
+pub fn top_k_from_reader<R: BufRead>(reader: R, k: usize) -> Result<Vec<ElfTotal>> {
+    let mut error = None;
+    let top = top_k(
+        elf_totals(reader).map_while(|elf| elf.map_err(|e| error = Some(e)).ok()),
+        k,
+    );
+    match error {
+        Some(e) => Err(e),
+        None => Ok(top),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{elf_calories, elf_totals, top_k, top_k_from_reader, Day1, ElfTotal};
+    use crate::solution::Solution;
+
+    const EXAMPLE: &str = "1000
 2000
 3000
 
@@ -62,7 +218,84 @@ fn example_test() {
 9000
 
 10000";
-    let mut elf_calories = dbg!(elf_calories(input).unwrap());
-    elf_calories.sort();
-    assert_eq!(elf_calories.last().unwrap().clone(), 24000);
+
+    #[test]
+    fn example_part1() {
+        let elf_totals = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(24000, Day1::part_one(&elf_totals).unwrap());
+    }
+
+    #[test]
+    fn example_part2() {
+        let elf_totals = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(45000, Day1::part_two(&elf_totals).unwrap());
+    }
+
+    #[test]
+    fn elf_totals_test() {
+        let totals = elf_totals("1\r\n2\r\n\r\n\r\n3\n\n".as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                ElfTotal {
+                    elf: 0,
+                    calories: 3
+                },
+                ElfTotal {
+                    elf: 1,
+                    calories: 3
+                }
+            ],
+            totals
+        );
+        // without a trailing separator the last elf still counts
+        assert_eq!(
+            4,
+            elf_totals(EXAMPLE.as_bytes()).last().unwrap().unwrap().elf
+        );
+
+        let error = elf_totals("1\n\n2\nabc\n".as_bytes())
+            .nth(1)
+            .unwrap()
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 4: invalid calories \"abc\""));
+    }
+
+    #[test]
+    fn elf_calories_test() {
+        let inventories = elf_calories(EXAMPLE).unwrap();
+        assert_eq!(5, inventories.len());
+        assert_eq!(vec![7000, 8000, 9000], inventories[3].items);
+        assert_eq!(24000, inventories[3].calories);
+        // both readers agree on the totals
+        assert_eq!(
+            elf_totals(EXAMPLE.as_bytes())
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            inventories.iter().map(|i| i.total()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn top_k_test() {
+        let top = top_k_from_reader(EXAMPLE.as_bytes(), 3).unwrap();
+        assert_eq!(
+            vec![(3, 24000), (2, 11000), (4, 10000)],
+            top.iter().map(|e| (e.elf, e.calories)).collect::<Vec<_>>()
+        );
+
+        let ties =
+            [(0, 5), (1, 7), (2, 7), (3, 1)].map(|(elf, calories)| ElfTotal { elf, calories });
+        assert_eq!(
+            vec![1, 2],
+            top_k(ties, 2).iter().map(|e| e.elf).collect::<Vec<_>>()
+        );
+        assert_eq!(4, top_k(ties, 10).len());
+        assert!(top_k(ties, 0).is_empty());
+
+        assert!(top_k_from_reader("1\nx\n".as_bytes(), 3).is_err());
+    }
 }
@@ -0,0 +1,283 @@
+use std::fmt::Display;
+
+use color_eyre::eyre::{eyre, Result};
+use serde::Serialize;
+
+use super::ElfInventory;
+
+/// Percentiles listed in the report
+pub const PERCENTILES: [f64; 5] = [10.0, 25.0, 75.0, 90.0, 99.0];
+
+/// One row per elf in the exported report
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ElfRow {
+    pub elf: usize,
+    pub items: usize,
+    pub calories: u64,
+    pub smallest_item: u64,
+    pub largest_item: u64,
+}
+
+impl From<&ElfInventory> for ElfRow {
+    fn from(inventory: &ElfInventory) -> Self {
+        ElfRow {
+            elf: inventory.elf,
+            items: inventory.items.len(),
+            calories: inventory.calories,
+            smallest_item: inventory.items.iter().copied().min().unwrap_or(0),
+            largest_item: inventory.items.iter().copied().max().unwrap_or(0),
+        }
+    }
+}
+
+/// Number of elves whose total falls in `start..end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: u64,
+    pub end: u64,
+    pub elves: usize,
+}
+
+/// Summary of the calories carried by all the elves
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub elves: usize,
+    pub items: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// Each of [`PERCENTILES`] with its value
+    pub percentiles: Vec<(f64, f64)>,
+    pub histogram: Vec<Bucket>,
+    /// Elves more than 1.5 interquartile ranges outside the middle half, most extreme first
+    pub outliers: Vec<ElfRow>,
+}
+
+impl Report {
+    pub fn new(inventories: &[ElfInventory], buckets: usize) -> Result<Self> {
+        if inventories.is_empty() {
+            return Err(eyre!("No elves found in input"));
+        }
+        if buckets == 0 {
+            return Err(eyre!("The histogram needs at least one bucket"));
+        }
+
+        let mut totals = inventories.iter().map(|i| i.calories).collect::<Vec<_>>();
+        totals.sort_unstable();
+        let min = totals[0];
+        let max = totals[totals.len() - 1];
+
+        let q1 = percentile(&totals, 25.0);
+        let q3 = percentile(&totals, 75.0);
+        let fence = 1.5 * (q3 - q1);
+        let median = percentile(&totals, 50.0);
+        let mut outliers = inventories
+            .iter()
+            .filter(|i| (i.calories as f64) < q1 - fence || (i.calories as f64) > q3 + fence)
+            .map(ElfRow::from)
+            .collect::<Vec<_>>();
+        outliers.sort_by(|a, b| {
+            (b.calories as f64 - median)
+                .abs()
+                .total_cmp(&(a.calories as f64 - median).abs())
+        });
+
+        Ok(Report {
+            elves: inventories.len(),
+            items: inventories.iter().map(|i| i.items.len()).sum(),
+            min,
+            max,
+            mean: totals.iter().map(|t| *t as f64).sum::<f64>() / totals.len() as f64,
+            median,
+            percentiles: PERCENTILES
+                .iter()
+                .map(|p| (*p, percentile(&totals, *p)))
+                .collect(),
+            histogram: histogram(&totals, buckets),
+            outliers,
+        })
+    }
+}
+
+/// Linearly interpolated between the closest ranks of the sorted, non-empty `values`
+fn percentile(sorted: &[u64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let below = sorted[rank.floor() as usize] as f64;
+    let above = sorted[rank.ceil() as usize] as f64;
+    below + (above - below) * rank.fract()
+}
+
+/// `buckets` equally wide buckets covering all of the sorted, non-empty `totals`. The bounds are
+/// worked out in u128 so totals near u64::MAX are counted, and saturate at u64::MAX
+fn histogram(sorted: &[u64], buckets: usize) -> Vec<Bucket> {
+    let min = sorted[0] as u128;
+    let width = (sorted[sorted.len() - 1] as u128 - min) / buckets as u128 + 1;
+    let saturate = |bound: u128| u64::try_from(bound).unwrap_or(u64::MAX);
+    (0..buckets as u128)
+        .map(|b| {
+            let start = min + b * width;
+            let end = start + width;
+            Bucket {
+                start: saturate(start),
+                end: saturate(end),
+                elves: sorted
+                    .iter()
+                    .filter(|t| (start..end).contains(&(**t as u128)))
+                    .count(),
+            }
+        })
+        .collect()
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Elves       | {:>12}", self.elves)?;
+        writeln!(f, "Items       | {:>12}", self.items)?;
+        writeln!(f, "Min         | {:>12}", self.min)?;
+        writeln!(f, "Max         | {:>12}", self.max)?;
+        writeln!(f, "Mean        | {:>12.1}", self.mean)?;
+        writeln!(f, "Median      | {:>12.1}", self.median)?;
+        for (p, value) in &self.percentiles {
+            writeln!(f, "{:<11} | {value:>12.1}", format!("p{p}"))?;
+        }
+
+        writeln!(f)?;
+        let most = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0);
+        for bucket in &self.histogram {
+            let bar = "#".repeat((bucket.elves * 40).div_ceil(most.max(1)));
+            writeln!(
+                f,
+                "{:>8}..{:<8} | {:>6} {bar}",
+                bucket.start, bucket.end, bucket.elves
+            )?;
+        }
+
+        writeln!(f)?;
+        if self.outliers.is_empty() {
+            write!(f, "No outliers")
+        } else {
+            write!(f, "Outliers:")?;
+            for row in &self.outliers {
+                write!(
+                    f,
+                    "\n  elf {:>6}: {} calories in {} items",
+                    row.elf, row.calories, row.items
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn to_csv(rows: &[ElfRow]) -> String {
+    let mut csv = String::from("elf,items,calories,smallest_item,largest_item\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            row.elf, row.items, row.calories, row.smallest_item, row.largest_item
+        ));
+    }
+    csv
+}
+
+pub fn to_json(rows: &[ElfRow]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(rows)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{histogram, percentile, to_csv, to_json, Bucket, ElfRow, Report};
+    use crate::day_1::elf_calories;
+
+    const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn report_test() {
+        let report = Report::new(&elf_calories(EXAMPLE).unwrap(), 3).unwrap();
+        assert_eq!((5, 10), (report.elves, report.items));
+        assert_eq!((4000, 24000), (report.min, report.max));
+        assert_eq!(11000.0, report.mean);
+        assert_eq!(10000.0, report.median);
+        assert_eq!((25.0, 6000.0), report.percentiles[1]);
+        assert_eq!(
+            vec![
+                Bucket {
+                    start: 4000,
+                    end: 10667,
+                    elves: 3
+                },
+                Bucket {
+                    start: 10667,
+                    end: 17334,
+                    elves: 1
+                },
+                Bucket {
+                    start: 17334,
+                    end: 24001,
+                    elves: 1
+                }
+            ],
+            report.histogram
+        );
+        // the fences are at -1500 and 18500
+        assert_eq!(
+            vec![3],
+            report.outliers.iter().map(|o| o.elf).collect::<Vec<_>>()
+        );
+        assert!(report
+            .to_string()
+            .contains("Outliers:\n  elf      3: 24000 calories in 3 items"));
+
+        assert!(Report::new(&[], 3).is_err());
+    }
+
+    #[test]
+    fn histogram_test() {
+        let totals = [0, u64::MAX - 1, u64::MAX];
+        let histogram = histogram(&totals, 2);
+        assert_eq!(
+            vec![1, 2],
+            histogram.iter().map(|b| b.elves).collect::<Vec<_>>()
+        );
+        assert_eq!(u64::MAX, histogram[1].end);
+    }
+
+    #[test]
+    fn percentile_test() {
+        assert_eq!(5.0, percentile(&[5], 90.0));
+        assert_eq!(2.5, percentile(&[1, 2, 3, 4], 50.0));
+        assert_eq!(4.0, percentile(&[1, 2, 3, 4], 100.0));
+    }
+
+    #[test]
+    fn export_test() {
+        let rows = elf_calories(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(ElfRow::from)
+            .collect::<Vec<_>>();
+        let csv = to_csv(&rows);
+        assert_eq!(6, csv.lines().count());
+        assert!(
+            csv.starts_with("elf,items,calories,smallest_item,largest_item\n0,3,6000,1000,3000\n")
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&rows).unwrap()).unwrap();
+        assert_eq!(24000, json[3]["calories"]);
+        assert_eq!(7000, json[3]["smallest_item"]);
+    }
+}
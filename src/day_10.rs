@@ -1,22 +1,35 @@
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
-
-    let input = include_str!("input/day_10.txt");
-    println!(
-        "Part 1: {}",
-        get_signal_strengths(input, &[20, 60, 100, 140, 180, 220])?
-            .into_iter()
-            .sum::<i64>()
-    );
-    println!("Part 2: \n{}", run_screen(input)?.display());
-    Ok(())
+use crate::solution::Solution;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Instruction>;
+    type PartOne = i64;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one(instructions: &Self::Input) -> Result<Self::PartOne> {
+        Ok(
+            get_signal_strengths(instructions, &[20, 60, 100, 140, 180, 220])
+                .into_iter()
+                .sum(),
+        )
+    }
+
+    fn part_two(instructions: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(run_screen(instructions).display())
+    }
 }
 
-fn run_screen(input: &str) -> Result<Screen> {
-    let instructions = parse_input(input)?;
+fn run_screen(instructions: &[Instruction]) -> Screen {
     let mut screen = Screen::new();
 
     let mut cpu = Cpu::new();
@@ -45,12 +58,10 @@ fn run_screen(input: &str) -> Result<Screen> {
         }
     }
 
-    Ok(screen)
+    screen
 }
 
-fn get_signal_strengths(input: &str, cycles: &[usize]) -> Result<Vec<i64>> {
-    let instructions = parse_input(input)?;
-
+fn get_signal_strengths(instructions: &[Instruction], cycles: &[usize]) -> Vec<i64> {
     let mut result = Vec::new();
 
     let mut cpu = Cpu::new();
@@ -81,7 +92,7 @@ fn get_signal_strengths(input: &str, cycles: &[usize]) -> Result<Vec<i64>> {
         }
     }
 
-    Ok(result)
+    result
 }
 
 fn parse_input(input: &str) -> Result<Vec<Instruction>> {
@@ -102,7 +113,7 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>> {
         .try_collect()
 }
 
-enum Instruction {
+pub enum Instruction {
     NoOp,
     Addx(i64),
 }
@@ -173,7 +184,7 @@ impl Cpu {
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_signal_strengths, run_screen};
+    use super::{get_signal_strengths, parse_input, run_screen};
 
     #[test]
     fn simple_example_test() {
@@ -183,7 +194,7 @@ addx -5";
 
         assert_eq!(
             vec![1, 2, 3, 4 * 4, 4 * 5],
-            get_signal_strengths(input, &[1, 2, 3, 4, 5]).unwrap()
+            get_signal_strengths(&parse_input(input).unwrap(), &[1, 2, 3, 4, 5])
         );
     }
 
@@ -193,7 +204,7 @@ addx -5";
 
         assert_eq!(
             vec![420, 1140, 1800, 2940, 2880, 3960],
-            get_signal_strengths(input, &[20, 60, 100, 140, 180, 220]).unwrap()
+            get_signal_strengths(&parse_input(input).unwrap(), &[20, 60, 100, 140, 180, 220])
         );
     }
 
@@ -201,7 +212,7 @@ addx -5";
     #[test]
     fn example_part2() {
         let input = include_str!("input/example_day_10.txt");
-        let result = run_screen(input).unwrap().display();
+        let result = run_screen(&parse_input(input).unwrap()).display();
         println!("{}", result);
         assert_eq!(
             "##..##..##..##..##..##..##..##..##..##..
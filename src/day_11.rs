@@ -1,34 +1,46 @@
 use std::collections::VecDeque;
 
-use color_eyre::eyre::{eyre, Result};
+use color_eyre::eyre::Result;
 use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{newline, space1},
     combinator::{map, value},
+    error::context,
     multi::separated_list0,
     sequence::{delimited, preceded, terminated, tuple},
-    IResult,
 };
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+use crate::{
+    parse::{self, ParseResult},
+    solution::Solution,
+};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<Monkey>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_monkeys(&input.replace("\r\n", "\n"))
+    }
+
+    fn part_one(monkeys: &Self::Input) -> Result<Self::PartOne> {
+        Ok(monkey_business(monkeys, 20))
+    }
 
-    let input = include_str!("input/day_11.txt");
-    println!(
-        "Part 1: {}",
-        monkey_business(&input.replace("\r\n", "\n"), 20)?
-    );
-    println!(
-        "Part 2: {}",
-        monkey_business(&input.replace("\r\n", "\n"), 10000)?
-    );
-    Ok(())
+    fn part_two(monkeys: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(monkey_business(monkeys, 10000))
+    }
 }
 
-fn monkey_business(input: &str, rounds: usize) -> Result<u64> {
-    let mut monkeys = parse_monkeys(input)?;
+fn monkey_business(monkeys: &[Monkey], rounds: usize) -> u64 {
+    let mut monkeys = monkeys.to_vec();
     monkeys.sort_by(|m1, m2| Ord::cmp(&m1.id, &m2.id));
     let magic_monkey_number: u64 = monkeys.iter().map(|m| m.divisible_test_number).product();
 
@@ -60,48 +72,50 @@ fn monkey_business(input: &str, rounds: usize) -> Result<u64> {
         // }
     }
 
-    Ok(monkeys
+    monkeys
         .iter()
         .map(|m| m.inspect_count)
         .sorted_by_key(|&n| std::cmp::Reverse(n))
         .take(2)
-        .product())
+        .product()
 }
 
 fn parse_monkeys(input: &str) -> Result<Vec<Monkey>> {
     input
         .split("\n\n")
-        .map(parse_monkey)
+        .map(|s| Ok(parse::finish(input, parse_monkey(s))?))
         .collect::<Result<Vec<_>>>()
 }
 
-fn parse_monkey(s: &str) -> Result<Monkey> {
-    let (_, (id, items, operation, (divisible_test_number, test_true_monkey, test_false_monkey))) =
+fn parse_monkey(s: &str) -> ParseResult<'_, Monkey> {
+    let (s, (id, items, operation, (divisible_test_number, test_true_monkey, test_false_monkey))) =
         tuple((
-            terminated(monkey_heading, newline),
-            terminated(starting_items, newline),
-            terminated(operation, newline),
-            test_parse,
-        ))(s)
-        .map_err(|e| eyre!("Could not parse :: {}", e))?;
-    Ok(Monkey {
-        id,
-        items,
-        operation,
-        divisible_test_number,
-        test_true_monkey,
-        test_false_monkey,
-        inspect_count: 0,
-    })
+            terminated(context("monkey heading", monkey_heading), newline),
+            terminated(context("starting items", starting_items), newline),
+            terminated(context("operation", operation), newline),
+            context("test", test_parse),
+        ))(s)?;
+    Ok((
+        s,
+        Monkey {
+            id,
+            items,
+            operation,
+            divisible_test_number,
+            test_true_monkey,
+            test_false_monkey,
+            inspect_count: 0,
+        },
+    ))
 }
 
-fn monkey_heading(input: &str) -> IResult<&str, u64> {
+fn monkey_heading(input: &str) -> ParseResult<'_, u64> {
     terminated(
         preceded(tag("Monkey "), nom::character::complete::u64),
         tag(":"),
     )(input)
 }
-fn starting_items(input: &str) -> IResult<&str, VecDeque<MonkeyItem>> {
+fn starting_items(input: &str) -> ParseResult<'_, VecDeque<MonkeyItem>> {
     preceded(
         tag("  Starting items: "),
         map(
@@ -113,7 +127,7 @@ fn starting_items(input: &str) -> IResult<&str, VecDeque<MonkeyItem>> {
         ),
     )(input)
 }
-fn operation(input: &str) -> IResult<&str, Operation> {
+fn operation(input: &str) -> ParseResult<'_, Operation> {
     let (input, (lhs, operation_type, rhs)) = preceded(
         tag("  Operation: new = "),
         tuple((
@@ -138,13 +152,13 @@ fn operation(input: &str) -> IResult<&str, Operation> {
         },
     ))
 }
-fn operand(input: &str) -> IResult<&str, Operand> {
+fn operand(input: &str) -> ParseResult<'_, Operand> {
     alt((
         value(Operand::Input, tag("old")),
         map(nom::character::complete::u64, Operand::Number),
     ))(input)
 }
-fn test_parse(input: &str) -> IResult<&str, (u64, u64, u64)> {
+fn test_parse(input: &str) -> ParseResult<'_, (u64, u64, u64)> {
     tuple((
         terminated(
             preceded(tag("  Test: divisible by "), nom::character::complete::u64),
@@ -164,8 +178,8 @@ fn test_parse(input: &str) -> IResult<&str, (u64, u64, u64)> {
     ))(input)
 }
 
-#[derive(PartialEq, Eq, Debug)]
-struct Monkey {
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Monkey {
     id: u64,
     items: VecDeque<MonkeyItem>,
     operation: Operation,
@@ -175,7 +189,7 @@ struct Monkey {
     inspect_count: u64,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 struct MonkeyItem {
     val: u64,
     visited_ids: Vec<u64>,
@@ -193,7 +207,7 @@ impl From<u64> for MonkeyItem {
 }
 
 impl Monkey {
-    pub(crate) fn find_to_toss_monkey(
+    fn find_to_toss_monkey(
         &self,
         rounds: usize,
         magic_monkey_number: u64,
@@ -239,7 +253,7 @@ impl Monkey {
 //     is_cycle
 // }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 struct Operation {
     lhs: Operand,
     rhs: Operand,
@@ -276,7 +290,7 @@ enum OperationType {
 mod tests {
     use std::collections::VecDeque;
 
-    use crate::{monkey_business, parse_monkeys, Monkey, Operand, Operation, OperationType};
+    use super::{monkey_business, parse_monkeys, Monkey, Operand, Operation, OperationType};
 
     #[test]
     fn parse_monkey_test() {
@@ -329,12 +343,15 @@ Monkey 1:
     #[test]
     fn example_part1() {
         let input = include_str!("input/example_day_11.txt").replace("\r\n", "\n");
-        assert_eq!(10605, monkey_business(&input, 20).unwrap());
+        assert_eq!(10605, monkey_business(&parse_monkeys(&input).unwrap(), 20));
     }
 
     #[test]
     fn example_part2() {
         let input = include_str!("input/example_day_11.txt").replace("\r\n", "\n");
-        assert_eq!(2713310158, monkey_business(&input, 10000).unwrap());
+        assert_eq!(
+            2713310158,
+            monkey_business(&parse_monkeys(&input).unwrap(), 10000)
+        );
     }
 }
@@ -9,77 +9,78 @@ use color_eyre::{
     Report,
 };
 use itertools::Itertools;
-fn main() -> Result<()> {
-    color_eyre::install()?;
-
-    let input = include_str!("input/day_12.txt");
-    let grid = Grid::from_str(input)?;
-    println!("Part 1: {}", find_shortest_path(&grid)?);
-    println!("Part 2: {}", find_shortest_start_for_path(&grid)?);
-    Ok(())
+
+use crate::{
+    grid::{Grid, Pos},
+    solution::Solution,
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = HeightMap;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        HeightMap::from_str(input)
+    }
+
+    fn part_one(height_map: &Self::Input) -> Result<Self::PartOne> {
+        find_shortest_path(height_map)
+    }
+
+    fn part_two(height_map: &Self::Input) -> Result<Self::PartTwo> {
+        find_shortest_start_for_path(height_map)
+    }
 }
 
-fn find_shortest_path(grid: &Grid) -> Result<u64> {
-    grid.distance_from_end
-        .get(&grid.start)
+fn find_shortest_path(height_map: &HeightMap) -> Result<u64> {
+    height_map
+        .distance_from_end
+        .get(&height_map.start)
         .cloned()
         .ok_or(eyre!("No path from start to end?"))
 }
 
-fn find_shortest_start_for_path(grid: &Grid) -> Result<u64> {
-    grid.distance_from_end
+fn find_shortest_start_for_path(height_map: &HeightMap) -> Result<u64> {
+    height_map
+        .distance_from_end
         .iter()
-        .filter(|(pos, _)| grid.points[pos.0][pos.1].0 == 0)
+        .filter(|(pos, _)| height_map.points[**pos].0 == 0)
         .min_by_key(|(_, d)| *d)
         .map(|(_, d)| d)
         .cloned()
         .ok_or(eyre!("Nothing in grid!"))
 }
 
-struct Grid {
-    width: usize,
-    height: usize,
-    points: Vec<Vec<GridPoint>>,
-    start: GridPos,
-    end: GridPos,
-    distance_from_end: HashMap<GridPos, u64>,
+pub struct HeightMap {
+    points: Grid<GridPoint>,
+    start: Pos,
+    end: Pos,
+    distance_from_end: HashMap<Pos, u64>,
 }
-impl FromStr for Grid {
+impl FromStr for HeightMap {
     type Err = Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s
-            .lines()
-            .next()
-            .map(|l| l.len())
-            .ok_or(eyre!("First row is empty!"))?;
-        let height = s.lines().count();
         let mut start = None;
         let mut end = None;
-        let points = s
-            .lines()
-            .enumerate()
-            .map(|(i, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(j, c)| {
-                        if c == 'S' {
-                            start = Some((i, j));
-                            GridPoint::from_char('a')
-                        } else if c == 'E' {
-                            end = Some((i, j));
-                            GridPoint::from_char('z')
-                        } else {
-                            GridPoint::from_char(c)
-                        }
-                    })
-                    .collect_vec()
+        let points = Grid::parse(s, |pos, c| {
+            Ok(if c == 'S' {
+                start = Some(pos);
+                GridPoint::from_char('a')
+            } else if c == 'E' {
+                end = Some(pos);
+                GridPoint::from_char('z')
+            } else {
+                GridPoint::from_char(c)
             })
-            .collect_vec();
+        })?;
 
-        Ok(Grid::new(
-            width,
-            height,
+        Ok(HeightMap::new(
             points,
             start.ok_or(eyre!("Start point not found in grid"))?,
             end.ok_or(eyre!("End point not found in grid"))?,
@@ -87,33 +88,22 @@ impl FromStr for Grid {
     }
 }
 
-impl Grid {
-    fn in_bound(&self, p: &GridPos) -> bool {
-        p.0 < self.height && p.1 < self.width
-    }
-
-    fn can_move(&self, start: &GridPos, end: &GridPos) -> bool {
-        if !self.in_bound(start) || !self.in_bound(end) {
+impl HeightMap {
+    fn can_move(&self, start: &Pos, end: &Pos) -> bool {
+        let (Some(start_point), Some(end_point)) = (self.points.get(*start), self.points.get(*end))
+        else {
             return false;
-        }
+        };
 
-        let end_height = self.points[end.0][end.1].0;
-        let start_height = self.points[start.0][start.1].0;
+        let end_height = end_point.0;
+        let start_height = start_point.0;
 
         // end_height <= start_height || start_height + 1 == end_height
         start_height.saturating_sub(1) == end_height || start_height <= end_height
     }
 
-    fn new(
-        width: usize,
-        height: usize,
-        points: Vec<Vec<GridPoint>>,
-        start: GridPos,
-        end: GridPos,
-    ) -> Self {
+    fn new(points: Grid<GridPoint>, start: Pos, end: Pos) -> Self {
         let mut r = Self {
-            width,
-            height,
             points,
             start,
             end,
@@ -124,7 +114,7 @@ impl Grid {
     }
 
     fn precompute_distance_from_end(&mut self) {
-        let mut visited: HashSet<GridPos> = HashSet::new();
+        let mut visited: HashSet<Pos> = HashSet::new();
 
         loop {
             // println!("Visited: {visited:?}");
@@ -143,14 +133,9 @@ impl Grid {
                 break;
             };
 
-            let neighbours = [
-                (current_node.0.saturating_sub(1), current_node.1),
-                (current_node.0 + 1, current_node.1),
-                (current_node.0, current_node.1.saturating_sub(1)),
-                (current_node.0, current_node.1 + 1),
-            ];
-            let neighbours = neighbours
-                .iter()
+            let neighbours = self
+                .points
+                .neighbours4(current_node)
                 .filter(|neighbour| {
                     !visited.contains(neighbour) && self.can_move(&current_node, neighbour)
                 })
@@ -158,7 +143,7 @@ impl Grid {
 
             for neighbour in neighbours {
                 self.distance_from_end
-                    .entry(*neighbour)
+                    .entry(neighbour)
                     .and_modify(|d| *d = Ord::min(*d, current_node_distance + 1))
                     .or_insert(current_node_distance + 1);
             }
@@ -169,7 +154,6 @@ impl Grid {
     }
 }
 
-type GridPos = (usize, usize);
 struct GridPoint(u8);
 impl GridPoint {
     fn from_char(c: char) -> Self {
@@ -177,25 +161,19 @@ impl GridPoint {
     }
 }
 
-impl Display for Grid {
+impl Display for GridPoint {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(
-            f,
-            "{}",
-            self.points
-                .iter()
-                .map(|line| line.iter().map(|i| (i.0 + b'a') as char).join(""))
-                .join("\n")
-        )?;
+        write!(f, "{}", (self.0 + b'a') as char)
+    }
+}
+
+impl Display for HeightMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.points)?;
         writeln!(f)?;
-        writeln!(
-            f,
-            "{}",
-            self.points
-                .iter()
-                .map(|line| line.iter().map(|i| format!(" {:2} ", i.0)).join(""))
-                .join("\n")
-        )?;
+        for row in self.points.rows() {
+            writeln!(f, "{}", row.map(|i| format!(" {:2} ", i.0)).join(""))?;
+        }
         Ok(())
     }
 }
@@ -204,7 +182,7 @@ impl Display for Grid {
 mod tests {
     use std::str::FromStr;
 
-    use crate::{find_shortest_path, find_shortest_start_for_path, Grid};
+    use super::{find_shortest_path, find_shortest_start_for_path, HeightMap};
 
     #[test]
     fn example_part1() {
@@ -216,7 +194,7 @@ abdefghi";
 
         assert_eq!(
             31,
-            find_shortest_path(&Grid::from_str(input).unwrap()).unwrap()
+            find_shortest_path(&HeightMap::from_str(input).unwrap()).unwrap()
         );
     }
 
@@ -230,7 +208,7 @@ abdefghi";
 
         assert_eq!(
             29,
-            find_shortest_start_for_path(&Grid::from_str(input).unwrap()).unwrap()
+            find_shortest_start_for_path(&HeightMap::from_str(input).unwrap()).unwrap()
         );
     }
 }
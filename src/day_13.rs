@@ -1,75 +1,72 @@
-use std::{cmp::Ordering, str::FromStr};
+use std::cmp::Ordering;
 
-use color_eyre::{
-    eyre::{eyre, Result},
-    Report,
-};
+use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 use nom::{
-    branch::alt, bytes::complete::tag, combinator::map, error::Error, multi::separated_list1,
-    sequence::delimited, Finish, IResult,
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{cut, map},
+    error::context,
+    multi::separated_list1,
+    sequence::delimited,
+};
+
+use crate::{
+    parse::{self, ParseResult},
+    solution::Solution,
 };
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<Packet>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let input = include_str!("input/day_13.txt");
-    println!(
-        "Part 1: {}",
-        indexes_in_right_order(input)?.iter().sum::<usize>()
-    );
-    println!("Part 2: {}", decoder_key(input)?);
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_packets(input)
+    }
+
+    fn part_one(packets: &Self::Input) -> Result<Self::PartOne> {
+        Ok(indexes_in_right_order(packets)?.iter().sum())
+    }
+
+    fn part_two(packets: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(decoder_key(packets))
+    }
 }
 
-fn decoder_key(input: &str) -> Result<usize> {
+fn decoder_key(packets: &[Packet]) -> usize {
     let divider_packets = [
         Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]),
         Packet::List(vec![Packet::List(vec![Packet::Integer(6)])]),
     ];
 
     let sorted_packets = divider_packets
-        .clone()
-        .map(Ok)
-        .into_iter()
-        .chain(
-            input
-                .lines()
-                .filter(|l| !l.is_empty())
-                .map(Packet::from_str),
-        )
-        .sorted_by(|p1, p2| {
-            if p1.is_ok() && p2.is_ok() {
-                match p1.as_ref().unwrap().compare(p2.as_ref().unwrap()) {
-                    PacketComparison::RightOrder => Ordering::Less,
-                    PacketComparison::Undecided => unreachable!(),
-                    PacketComparison::WrongOrder => Ordering::Greater,
-                }
-            } else {
-                Ordering::Equal // if its broke, dont fix it
-            }
+        .iter()
+        .chain(packets)
+        .sorted_by(|p1, p2| match p1.compare(p2) {
+            PacketComparison::RightOrder => Ordering::Less,
+            PacketComparison::Undecided => unreachable!(),
+            PacketComparison::WrongOrder => Ordering::Greater,
         })
-        .collect::<Result<Vec<_>>>()?;
+        .collect_vec();
 
-    Ok(divider_packets
+    divider_packets
         .iter()
-        .filter_map(|d| sorted_packets.iter().find_position(|p| *p == d))
+        .filter_map(|d| sorted_packets.iter().find_position(|p| **p == d))
         .map(|(i, _)| i + 1)
-        .product())
+        .product()
 }
 
-fn indexes_in_right_order(input: &str) -> Result<Vec<usize>> {
-    input
-        .replace("\r\n", "\n")
-        .split("\n\n")
-        .map(|pair| {
-            let (first, second) = pair
-                .split_once('\n')
-                .ok_or(eyre!("Found just one packet separated by spaces?"))?;
-            // println!("First: {first}, second: {second}");
-            let first = Packet::from_str(first)?;
-            let second = Packet::from_str(second)?;
-            Ok(first.compare(&second))
+fn indexes_in_right_order(packets: &[Packet]) -> Result<Vec<usize>> {
+    packets
+        .chunks(2)
+        .map(|pair| match pair {
+            [first, second] => Ok(first.compare(second)),
+            _ => Err(eyre!("Found just one packet separated by spaces?")),
         })
         .enumerate()
         .filter_map(|(i, comparison)| match comparison {
@@ -86,7 +83,7 @@ fn indexes_in_right_order(input: &str) -> Result<Vec<usize>> {
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
-enum Packet {
+pub enum Packet {
     Integer(u64),
     List(Vec<Packet>),
 }
@@ -144,33 +141,36 @@ impl Packet {
         }
     }
 }
-impl FromStr for Packet {
-    type Err = Report;
-    fn from_str(input: &str) -> Result<Self> {
-        parse_packet(input)
-            .finish()
-            .map(|(_, p)| p)
-            .map_err(|e| eyre!("Parsing Error!\n{}\n\n", e))
-    }
+fn parse_packets(input: &str) -> Result<Vec<Packet>> {
+    input
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| Ok(parse::finish(input, parse_packet(l))?))
+        .collect()
 }
 
-fn parse_packet(s: &str) -> IResult<&str, Packet> {
-    alt((
-        map(tag::<&str, &str, Error<_>>("[]"), |_| {
-            Packet::List(Vec::new())
-        }),
-        map(nom::character::complete::u64, Packet::Integer),
-        delimited(
-            tag("["),
-            map(separated_list1(tag(","), parse_packet), Packet::List),
-            tag("]"),
-        ),
-    ))(s)
+fn parse_packet(s: &str) -> ParseResult<'_, Packet> {
+    context(
+        "packet",
+        alt((
+            map(tag("[]"), |_| Packet::List(Vec::new())),
+            map(nom::character::complete::u64, Packet::Integer),
+            // `[]` was matched above, so anything after `[` has to be a packet
+            delimited(
+                tag("["),
+                cut(map(
+                    separated_list1(tag(","), cut(parse_packet)),
+                    Packet::List,
+                )),
+                cut(tag("]")),
+            ),
+        )),
+    )(s)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{decoder_key, indexes_in_right_order};
+    use super::{decoder_key, indexes_in_right_order, parse_packets};
 
     #[test]
     fn example_part1() {
@@ -198,7 +198,16 @@ mod tests {
 [1,[2,[3,[4,[5,6,7]]]],8,9]
 [1,[2,[3,[4,[5,6,0]]]],8,9]";
 
-        assert_eq!(vec![1, 2, 4, 6], indexes_in_right_order(input).unwrap());
+        assert_eq!(
+            vec![1, 2, 4, 6],
+            indexes_in_right_order(&parse_packets(input).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = parse_packets("[1,2]\n[3,[4,x]]\n").unwrap_err();
+        assert!(error.to_string().starts_with("line 2, column 7: expected"));
     }
 
     #[test]
@@ -227,6 +236,6 @@ mod tests {
 [1,[2,[3,[4,[5,6,7]]]],8,9]
 [1,[2,[3,[4,[5,6,0]]]],8,9]";
 
-        assert_eq!(140, decoder_key(input).unwrap());
+        assert_eq!(140, decoder_key(&parse_packets(input).unwrap()));
     }
 }
@@ -1,29 +1,41 @@
 use std::collections::HashSet;
 
-use color_eyre::eyre::{eyre, Result};
+use color_eyre::eyre::Result;
 use itertools::{Either, Itertools};
 use nom::{
-    bytes::complete::tag, combinator::map, error::Error, multi::separated_list0, sequence::tuple,
-    Finish,
+    bytes::complete::tag, combinator::map, error::context, multi::separated_list0, sequence::tuple,
 };
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
-
-    let input = include_str!("input/day_14.txt");
-    println!(
-        "Part 1: {}",
-        fill_with_sand(input, FillWithSandUntil::RockBoundary)?
-    );
-    println!(
-        "Part 2: {}",
-        fill_with_sand(input, FillWithSandUntil::Floor)?
-    );
-    Ok(())
+use crate::{
+    grid::SparseGrid,
+    parse::{self, ParseResult},
+    solution::Solution,
+};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Cave;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Cave::new(input)
+    }
+
+    fn part_one(cave: &Self::Input) -> Result<Self::PartOne> {
+        Ok(fill_with_sand(cave, FillWithSandUntil::RockBoundary))
+    }
+
+    fn part_two(cave: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(fill_with_sand(cave, FillWithSandUntil::Floor))
+    }
 }
 
-fn fill_with_sand(input: &str, until: FillWithSandUntil) -> Result<usize> {
-    let mut cave = Cave::new(input)?;
+fn fill_with_sand(cave: &Cave, until: FillWithSandUntil) -> usize {
+    let mut cave = cave.clone();
     // println!("{cave}");
 
     loop {
@@ -64,30 +76,38 @@ fn fill_with_sand(input: &str, until: FillWithSandUntil) -> Result<usize> {
         let should_sand_not_be_added = match until {
             FillWithSandUntil::RockBoundary => falling, // if sand is falling still, means we've
             // gone past rock boundary
-            FillWithSandUntil::Floor => cave.sand.contains(&cave.sand_source),
+            FillWithSandUntil::Floor => cave.tiles[cave.sand_source] == Tile::Sand,
         };
 
         if should_sand_not_be_added {
             cave.terminal_sand_path = sand_path;
             break;
         } else {
-            cave.sand.insert(sand_position);
+            cave.tiles[sand_position] = Tile::Sand;
+            cave.sand_count += 1;
         }
     }
     // println!("\n\n{cave}");
 
-    Ok(cave.sand.len())
+    cave.sand_count
 }
 
 const FLOOR_OFFSET: usize = 2;
 type CavePos = (usize, usize);
-struct Cave {
+#[derive(Clone)]
+pub struct Cave {
     sand_source: CavePos,
-    rocks: HashSet<CavePos>,
-    sand: HashSet<CavePos>,
+    tiles: SparseGrid<Tile>,
+    sand_count: usize,
     bottom_boundary: usize,
     terminal_sand_path: Vec<CavePos>,
 }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
 enum FillWithSandUntil {
     RockBoundary,
     Floor,
@@ -96,25 +116,41 @@ enum FillWithSandUntil {
 impl Cave {
     fn new(rock_paths: &str) -> Result<Self> {
         let rocks = parse_rocks(rock_paths)?;
+        let sand_source = (500, 0);
         let bottom_boundary = rocks.iter().map(|(_, y)| y).max().cloned().unwrap_or(0);
+        let floor = bottom_boundary + FLOOR_OFFSET;
+        // sand piles up at most as wide as it is high, so stays within `floor` of the source
+        let width = rocks
+            .iter()
+            .map(|(x, _)| *x)
+            .chain([sand_source.0 + floor])
+            .max()
+            .unwrap_or(0)
+            + 2;
+
+        let mut tiles = SparseGrid::sparse(width, floor + 1, Tile::Air);
+        for rock in rocks {
+            tiles[rock] = Tile::Rock;
+        }
+
         Ok(Self {
-            sand_source: (500, 0),
-            rocks,
-            sand: HashSet::new(),
+            sand_source,
+            tiles,
+            sand_count: 0,
             bottom_boundary,
             terminal_sand_path: Vec::new(),
         })
     }
 
     fn occupied(&self, pos: &CavePos) -> bool {
-        self.sand.contains(pos) || self.rocks.contains(pos)
+        self.tiles.get(*pos).is_some_and(|tile| *tile != Tile::Air)
     }
 }
 
 fn parse_rocks(input: &str) -> Result<HashSet<CavePos>> {
     input
         .lines()
-        .map(parse_rock_paths)
+        .map(|l| parse::finish(input, parse_rock_paths(l)))
         .map(|paths| {
             paths.map(|p| {
                 p.into_iter().tuple_windows().flat_map(|(pos1, pos2)| {
@@ -137,48 +173,41 @@ fn parse_rocks(input: &str) -> Result<HashSet<CavePos>> {
             })
         })
         .flatten_ok()
-        .collect::<Result<HashSet<_>>>()
+        .collect::<Result<HashSet<_>, _>>()
+        .map_err(Into::into)
 }
 
-fn parse_rock_paths(input: &str) -> Result<Vec<CavePos>> {
+// 498,4 -> 498,6 -> 496,6
+fn parse_rock_paths(input: &str) -> ParseResult<'_, Vec<CavePos>> {
     separated_list0(
-        tag::<_, _, Error<&str>>(" -> "),
-        map(
-            tuple((
-                nom::character::complete::u64,
-                tag(","),
-                nom::character::complete::u64,
-            )),
-            |(a, _, b)| (a as usize, b as usize),
+        tag(" -> "),
+        context(
+            "position",
+            map(
+                tuple((
+                    nom::character::complete::u64,
+                    tag(","),
+                    nom::character::complete::u64,
+                )),
+                |(a, _, b)| (a as usize, b as usize),
+            ),
         ),
     )(input)
-    .finish()
-    .map(|(_, paths)| paths)
-    .map_err(|e| eyre!("Parse Error:\n{e}\n\n"))
 }
 
 impl std::fmt::Display for Cave {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // let (left_boundary, right_boundary) = match self.rocks.iter().map(|(x, _)| x).minmax() {
-        //     MinMaxResult::NoElements => (0, 0),
-        //     MinMaxResult::OneElement(i) => (*i, *i),
-        //     MinMaxResult::MinMax(i, j) => (*i, *j),
-        // };
         let (left_boundary, right_boundary) =
             (500 - self.bottom_boundary, 500 + self.bottom_boundary);
 
         for y in 0..=(self.bottom_boundary + 1) {
             for x in left_boundary..=right_boundary {
-                let c = if self.sand_source == (x, y) {
-                    "+"
-                } else if self.rocks.contains(&(x, y)) {
-                    "#"
-                } else if self.sand.contains(&(x, y)) {
-                    "o"
-                } else if self.terminal_sand_path.contains(&(x, y)) {
-                    "~"
-                } else {
-                    "."
+                let c = match self.tiles.get((x, y)) {
+                    _ if self.sand_source == (x, y) => "+",
+                    Some(Tile::Rock) => "#",
+                    Some(Tile::Sand) => "o",
+                    _ if self.terminal_sand_path.contains(&(x, y)) => "~",
+                    _ => ".",
                 };
                 write!(f, "{}", c)?;
             }
@@ -190,7 +219,7 @@ impl std::fmt::Display for Cave {
 
 #[cfg(test)]
 mod tests {
-    use crate::{fill_with_sand, FillWithSandUntil};
+    use super::{fill_with_sand, Cave, FillWithSandUntil};
 
     #[test]
     fn example_part1() {
@@ -199,7 +228,7 @@ mod tests {
 
         assert_eq!(
             24,
-            fill_with_sand(input, FillWithSandUntil::RockBoundary).unwrap()
+            fill_with_sand(&Cave::new(input).unwrap(), FillWithSandUntil::RockBoundary)
         );
     }
     #[test]
@@ -207,6 +236,9 @@ mod tests {
         let input = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
 
-        assert_eq!(93, fill_with_sand(input, FillWithSandUntil::Floor).unwrap());
+        assert_eq!(
+            93,
+            fill_with_sand(&Cave::new(input).unwrap(), FillWithSandUntil::Floor)
+        );
     }
 }
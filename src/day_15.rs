@@ -1,31 +1,41 @@
-use std::{
-    cmp::{max, min},
-    fmt,
-    ops::RangeInclusive,
-};
+use std::fmt;
 
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     combinator::map,
+    error::context,
     sequence::{preceded, separated_pair, tuple},
-    Finish, IResult,
 };
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+use crate::{
+    interval::{Interval, IntervalSet},
+    parse::{self, ParseResult},
+    point::Vec2,
+    solution::Solution,
+};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<Sensor>;
+    type PartOne = isize;
+    type PartTwo = isize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_sensors(input)
+    }
+
+    fn part_one(sensors: &Self::Input) -> Result<Self::PartOne> {
+        columns_without_beacon_optimized(sensors, 2000000)
+    }
 
-    let input = include_str!("input/day_15.txt");
-    println!(
-        "Part 1: {}",
-        columns_without_beacon_optimized(input, 2000000)?
-    );
-    println!(
-        "Part 2: {}",
-        search_for_distress_beacon(input, 0, 4000000).unwrap()
-    );
-    Ok(())
+    fn part_two(sensors: &Self::Input) -> Result<Self::PartTwo> {
+        search_for_distress_beacon(sensors, 0, 4000000)
+    }
 }
 
 // fn columns_without_beacon(input: &str, y: isize) -> Result<u64> {
@@ -57,7 +67,7 @@ fn main() -> Result<()> {
 //         }
 //         if sensors
 //             .iter()
-//             .any(|s| s.position.distance_to(&current_point) <= s.nearest_beacon_distance)
+//             .any(|s| s.position.manhattan(current_point) <= s.nearest_beacon_distance)
 //         {
 //             covered_by_beacon += 1;
 //             // println!("Covered: ({}, {})", current_point.x, current_point.y);
@@ -82,7 +92,7 @@ fn main() -> Result<()> {
 //     //                 "B"
 //     //             } else if sensors
 //     //                 .iter()
-//     //                 .any(|s| s.position.distance_to(&curr) <= s.nearest_beacon_distance)
+//     //                 .any(|s| s.position.manhattan(curr) <= s.nearest_beacon_distance)
 //     //             {
 //     //                 "#"
 //     //             } else {
@@ -96,9 +106,7 @@ fn main() -> Result<()> {
 //     Ok(covered_by_beacon)
 // }
 
-fn columns_without_beacon_optimized(input: &str, y: isize) -> Result<isize> {
-    let sensors = parse_sensors(input)?;
-
+fn columns_without_beacon_optimized(sensors: &[Sensor], y: isize) -> Result<isize> {
     let (mut left_boundary, mut right_boundary) =
         match sensors.iter().map(|s| s.nearest_beacon.x).minmax() {
             itertools::MinMaxResult::NoElements => (0, 0),
@@ -110,80 +118,64 @@ fn columns_without_beacon_optimized(input: &str, y: isize) -> Result<isize> {
         .map(|s| s.nearest_beacon_distance)
         .max()
         .unwrap_or(0);
-    left_boundary -= wiggle_room as isize;
-    right_boundary += wiggle_room as isize;
+    left_boundary = left_boundary
+        .checked_sub(wiggle_room)
+        .ok_or_else(|| eyre!("Sensors are too far left"))?;
+    right_boundary = right_boundary
+        .checked_add(wiggle_room)
+        .ok_or_else(|| eyre!("Sensors are too far right"))?;
     // println!("{left_boundary}, {right_boundary}, {wiggle_room}");
     // let (left_boundary, right_boundary) = (-10_000_000, 20_000_000);
 
-    let covered_by_beacon = get_covered_boundaries(&sensors, y, left_boundary, right_boundary)
-        // .inspect(|r| println!("Coalesced: {}->{} ", r.start(), r.end()))
-        .map(|r| -> isize {
-            r.end() - r.start() + 1
+    let covered_by_beacon = get_covered_boundaries(sensors, y, left_boundary, right_boundary)
+        .intervals()
+        .iter()
+        .map(|r| -> Result<isize> {
+            let len = r
+                .len()
+                .ok_or_else(|| eyre!("Covered columns {r} are too many to count"))?;
+            Ok(len
                 - (sensors
                     .iter()
-                    .filter(|s| s.position.y == y && r.contains(&s.position.x))
-                    .map(|s| s.position.clone())
+                    .filter(|s| s.position.y == y && r.contains(s.position.x))
+                    .map(|s| s.position)
                     .chain(
                         sensors
                             .iter()
-                            .filter(|s| s.nearest_beacon.y == y && r.contains(&s.nearest_beacon.x))
-                            .map(|s| s.nearest_beacon.clone()),
+                            .filter(|s| s.nearest_beacon.y == y && r.contains(s.nearest_beacon.x))
+                            .map(|s| s.nearest_beacon),
                     )
                     .unique()
-                    .count()) as isize
+                    .count()) as isize)
         })
-        .sum::<isize>();
+        .sum::<Result<isize>>();
 
-    Ok(covered_by_beacon)
+    covered_by_beacon
 }
 
+/// Columns of row `y` between the boundaries that are within range of a sensor
 fn get_covered_boundaries(
     sensors: &[Sensor],
     y: isize,
     left_boundary: isize,
     right_boundary: isize,
-) -> impl Iterator<Item = RangeInclusive<isize>> {
-    sensors
+) -> IntervalSet<isize> {
+    let covered = sensors
         .iter()
         .flat_map(|s| s.row_boundary(y))
-        .filter_map(|r| {
-            if r.start() < &left_boundary {
-                if r.contains(&left_boundary) {
-                    return Some(left_boundary..=*r.end());
-                } else {
-                    None
-                }
-            } else if r.end() > &right_boundary {
-                if r.contains(&right_boundary) {
-                    return Some(*r.start()..=right_boundary);
-                } else {
-                    None
-                }
-            } else {
-                Some(r)
-            }
-        })
-        .sorted_by_key(|s| *s.start())
-        .coalesce(|l, r| {
-            if l.contains(r.start()) || l.contains(r.end()) {
-                Ok(min(*l.start(), *r.start())..=max(*l.end(), *r.end()))
-            } else if l.end() + 1 == *r.start() {
-                Ok(*l.start()..=*r.end())
-            } else {
-                Err((l, r))
-            }
-        })
+        .collect::<IntervalSet<_>>();
+    match Interval::new(left_boundary, right_boundary) {
+        Some(bounds) => covered.intersection(&bounds.into()),
+        None => IntervalSet::new(),
+    }
 }
 
-fn search_for_distress_beacon(input: &str, start: isize, end: isize) -> Result<isize> {
-    let sensors = parse_sensors(input)?;
-
+fn search_for_distress_beacon(sensors: &[Sensor], start: isize, end: isize) -> Result<isize> {
+    let bounds = Interval::new(start, end).ok_or_else(|| eyre!("Empty search area"))?;
     for y in start..=end {
-        if let Some(second_range) = get_covered_boundaries(&sensors, y, start, end).nth(1) {
-            let point = Point {
-                x: second_range.start() - 1,
-                y,
-            };
+        let covered = get_covered_boundaries(sensors, y, start, end);
+        if let Some(gap) = covered.complement(bounds).intervals().first() {
+            let point = Point::new(gap.start(), y);
             return Ok(point.x * 4000000 + point.y);
         }
     }
@@ -193,71 +185,57 @@ fn search_for_distress_beacon(input: &str, start: isize, end: isize) -> Result<i
 fn parse_sensors(input: &str) -> Result<Vec<Sensor>> {
     input
         .lines()
-        .map(|l| {
-            parse_sensor(l)
-                .finish()
-                .map(|(_, s)| s)
-                .map_err(|e| eyre!("Parse error:\n{e}"))
-        })
+        .map(|l| Ok(parse::finish(input, parse_sensor(l))?))
         .collect::<Result<Vec<_>>>()
 }
 // Sensor at x=9, y=16: closest beacon is at x=10, y=16
-fn parse_sensor(line: &str) -> IResult<&str, Sensor> {
+fn parse_sensor(line: &str) -> ParseResult<'_, Sensor> {
     map(
         tuple((
-            preceded(tag("Sensor at "), parse_point),
-            preceded(tag(": closest beacon is at "), parse_point),
+            preceded(tag("Sensor at "), context("sensor position", parse_point)),
+            preceded(
+                tag(": closest beacon is at "),
+                context("beacon position", parse_point),
+            ),
         )),
         |(s, b)| Sensor::new(s, b),
     )(line)
 }
-fn parse_point(s: &str) -> IResult<&str, Point> {
+fn parse_point(s: &str) -> ParseResult<'_, Point> {
     map(
         separated_pair(
             preceded(tag("x="), nom::character::complete::i64),
             tag(", "),
             preceded(tag("y="), nom::character::complete::i64),
         ),
-        |(x, y)| Point {
-            x: x as isize,
-            y: y as isize,
-        },
+        |(x, y)| Point::new(x as isize, y as isize),
     )(s)
 }
 
-#[derive(PartialEq, Eq, Debug, Hash, Clone)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-impl Point {
-    fn distance_to(&self, other: &Self) -> usize {
-        (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
-    }
-}
-struct Sensor {
+type Point = Vec2<isize>;
+pub struct Sensor {
     position: Point,
     nearest_beacon: Point,
-    nearest_beacon_distance: usize,
+    nearest_beacon_distance: isize,
 }
 impl Sensor {
     fn new(position: Point, nearest_beacon: Point) -> Self {
         Self {
-            nearest_beacon_distance: position.distance_to(&nearest_beacon),
+            nearest_beacon_distance: position.manhattan(nearest_beacon),
             position,
             nearest_beacon,
         }
     }
 
-    fn row_boundary(&self, row: isize) -> Option<RangeInclusive<isize>> {
-        let offset = self.position.y.abs_diff(row);
+    fn row_boundary(&self, row: isize) -> Option<Interval<isize>> {
+        let offset = (self.position.y - row).abs();
         if offset > self.nearest_beacon_distance {
             return None;
         }
         let distance_adjustment = self.nearest_beacon_distance - offset;
-        Some(
-            (self.position.x - distance_adjustment as isize)
-                ..=(self.position.x + distance_adjustment as isize),
+        Interval::new(
+            self.position.x - distance_adjustment,
+            self.position.x + distance_adjustment,
         )
     }
 }
@@ -274,7 +252,7 @@ impl fmt::Display for Sensor {
 
 #[cfg(test)]
 mod tests {
-    use crate::{columns_without_beacon_optimized, search_for_distress_beacon};
+    use super::{columns_without_beacon_optimized, parse_sensors, search_for_distress_beacon};
 
     #[test]
     fn example_part1() {
@@ -294,7 +272,21 @@ Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
         // assert_eq!(26, columns_without_beacon(input, 10).unwrap());
-        assert_eq!(26, columns_without_beacon_optimized(input, 10).unwrap());
+        assert_eq!(
+            26,
+            columns_without_beacon_optimized(&parse_sensors(input).unwrap(), 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn extreme_coordinates_test() {
+        // the covered part of the row has more columns than an isize can count
+        let input = "Sensor at x=-4611686018427387904, y=0: closest beacon is at x=0, y=0
+Sensor at x=4611686018427387903, y=0: closest beacon is at x=0, y=0";
+        assert!(columns_without_beacon_optimized(&parse_sensors(input).unwrap(), 0).is_err());
+        // the search would go past isize::MAX
+        let input = "Sensor at x=0, y=0: closest beacon is at x=9223372036854775807, y=0";
+        assert!(columns_without_beacon_optimized(&parse_sensors(input).unwrap(), 0).is_err());
     }
 
     #[test]
@@ -314,6 +306,9 @@ Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
-        assert_eq!(56000011, search_for_distress_beacon(input, 0, 20).unwrap());
+        assert_eq!(
+            56000011,
+            search_for_distress_beacon(&parse_sensors(input).unwrap(), 0, 20).unwrap()
+        );
     }
 }
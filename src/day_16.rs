@@ -2,35 +2,49 @@ use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
 use itertools::Itertools;
-use nom::bytes::complete::{tag, take};
-use nom::{branch::alt, error::Error, multi::separated_list0, sequence::tuple};
+use nom::bytes::complete::tag;
+use nom::character::complete::anychar;
+use nom::{branch::alt, combinator::map, error::context, multi::separated_list0, sequence::tuple};
 use petgraph::algo::floyd_warshall;
 use petgraph::prelude::*;
 
 use color_eyre::eyre::{eyre, Result};
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+use crate::{
+    parse::{self, ParseResult},
+    solution::Solution,
+};
 
-    let input = include_str!("input/day_16.txt");
-    println!("Part 1: {}", get_max_possible_pressure_released(input)?);
-    println!(
-        "Part 2: {}",
-        get_max_possible_pressure_with_elephant(input)?
-    );
-    Ok(())
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = DiGraphMap<Tunnel, u64>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_tunnels(input)
+    }
+
+    fn part_one(tunnels: &Self::Input) -> Result<Self::PartOne> {
+        get_max_possible_pressure_released(tunnels)
+    }
+
+    fn part_two(tunnels: &Self::Input) -> Result<Self::PartTwo> {
+        get_max_possible_pressure_with_elephant(tunnels)
+    }
 }
 
 const MAX_TIME: u64 = 30;
 
-fn get_max_possible_pressure_released(input: &str) -> Result<u64> {
-    let tunnels = parse_tunnels(input)?;
-
+fn get_max_possible_pressure_released(tunnels: &DiGraphMap<Tunnel, u64>) -> Result<u64> {
     let current_time = 0;
     let total_released_pressure = 0;
     let current_tunnel = tunnels.nodes().find(|t| t.valve == ['A', 'A']).unwrap();
 
-    let cost_matrix = floyd_warshall(&tunnels, |edge| {
+    let cost_matrix = floyd_warshall(tunnels, |edge| {
         if tunnels.contains_edge(edge.source(), edge.target()) {
             1
         } else {
@@ -44,7 +58,7 @@ fn get_max_possible_pressure_released(input: &str) -> Result<u64> {
         .filter(|t| t.flow_rate > 0)
         .map(|node| {
             find_max_releasable_pressure_if_moved(
-                &tunnels,
+                tunnels,
                 &cost_matrix,
                 &current_tunnel,
                 &node,
@@ -57,15 +71,13 @@ fn get_max_possible_pressure_released(input: &str) -> Result<u64> {
         .unwrap_or(0))
 }
 
-fn get_max_possible_pressure_with_elephant(input: &str) -> Result<u64> {
-    let tunnels = parse_tunnels(input)?;
-
+fn get_max_possible_pressure_with_elephant(tunnels: &DiGraphMap<Tunnel, u64>) -> Result<u64> {
     let current_time = 4;
     let total_released_pressure = 0;
     let current_tunnel = tunnels.nodes().find(|t| t.valve == ['A', 'A']).unwrap();
     let to_open_tunnels = tunnels.nodes().filter(|t| t.flow_rate > 0).collect_vec();
 
-    let cost_matrix = floyd_warshall(&tunnels, |edge| {
+    let cost_matrix = floyd_warshall(tunnels, |edge| {
         if tunnels.contains_edge(edge.source(), edge.target()) {
             1
         } else {
@@ -75,19 +87,17 @@ fn get_max_possible_pressure_with_elephant(input: &str) -> Result<u64> {
     .map_err(|e| eyre!("Negative cycle found! {e:?}"))?;
 
     Ok(all_combinations_split_in_2(&to_open_tunnels)
-        .filter(|(s1, s2)| s1.len() > (to_open_tunnels.len() / 2 - 1) && s2.len() > (to_open_tunnels.len() / 2 - 1))
-        .enumerate()
-        .map(|(_i, (s1, s2))| {
-            // if _i % 1000 == 0 {
-            //     println!("  At {_i}...");
-            // }
+        .filter(|(s1, s2)| {
+            s1.len() > (to_open_tunnels.len() / 2 - 1) && s2.len() > (to_open_tunnels.len() / 2 - 1)
+        })
+        .map(|(s1, s2)| {
             tunnels
                 .nodes()
                 .filter(|t| t.flow_rate > 0)
                 .filter(|t| !s1.contains(t))
                 .map(|node| {
                     find_max_releasable_pressure_if_moved(
-                        &tunnels,
+                        tunnels,
                         &cost_matrix,
                         &current_tunnel,
                         &node,
@@ -104,7 +114,7 @@ fn get_max_possible_pressure_with_elephant(input: &str) -> Result<u64> {
                     .filter(|t| !s2.contains(t))
                     .map(|node| {
                         find_max_releasable_pressure_if_moved(
-                            &tunnels,
+                            tunnels,
                             &cost_matrix,
                             &current_tunnel,
                             &node,
@@ -198,7 +208,7 @@ fn parse_tunnels(s: &str) -> Result<DiGraphMap<Tunnel, u64>> {
     let mut result = DiGraphMap::new();
     let mut paths: Vec<(Tunnel, [char; 2])> = Vec::new();
     for line in s.lines() {
-        let (valve, rate, tunnel_paths) = parse_tunnel_line_description(line)?;
+        let (valve, rate, tunnel_paths) = parse::finish(s, parse_tunnel_line_description(line))?;
         let tunnel = Tunnel::new(valve, rate);
         result.add_node(tunnel);
 
@@ -220,34 +230,30 @@ fn parse_tunnels(s: &str) -> Result<DiGraphMap<Tunnel, u64>> {
 
     Ok(result)
 }
-fn parse_tunnel_line_description(l: &str) -> Result<([char; 2], u64, std::vec::Vec<[char; 2]>)> {
-    tuple((
-        tag::<_, _, Error<&str>>("Valve "),
-        take(2usize),
-        tag(" has flow rate="),
-        nom::character::complete::u64,
-        alt((
-            tag("; tunnels lead to valves "),
-            tag("; tunnel leads to valve "),
+// Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+fn parse_tunnel_line_description(l: &str) -> ParseResult<'_, ([char; 2], u64, Vec<[char; 2]>)> {
+    map(
+        tuple((
+            tag("Valve "),
+            context("valve", valve),
+            tag(" has flow rate="),
+            context("flow rate", nom::character::complete::u64),
+            alt((
+                tag("; tunnels lead to valves "),
+                tag("; tunnel leads to valve "),
+            )),
+            separated_list0(tag(", "), context("valve", valve)),
         )),
-        separated_list0(tag(", "), take(2usize)),
-    ))(l)
-    .map(|(_, o)| o)
-    .map(|(_, valve, _, rate, _, paths)| {
-        (
-            [valve.chars().next().unwrap(), valve.chars().nth(1).unwrap()],
-            rate,
-            paths
-                .into_iter()
-                .map(|path| [path.chars().next().unwrap(), path.chars().nth(1).unwrap()])
-                .collect_vec(),
-        )
-    })
-    .map_err(|e| eyre!("Parsing Error::{}\n\n", e))
+        |(_, valve, _, rate, _, paths)| (valve, rate, paths),
+    )(l)
+}
+
+fn valve(s: &str) -> ParseResult<'_, [char; 2]> {
+    map(tuple((anychar, anychar)), |(a, b)| [a, b])(s)
 }
 
 #[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
-struct Tunnel {
+pub struct Tunnel {
     valve: [char; 2],
     flow_rate: u64,
 }
@@ -293,7 +299,9 @@ fn all_combinations_split_in_2<T: Copy + Eq + Hash>(
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_max_possible_pressure_released, get_max_possible_pressure_with_elephant};
+    use super::{
+        get_max_possible_pressure_released, get_max_possible_pressure_with_elephant, parse_tunnels,
+    };
 
     #[test]
     fn example_part1() {
@@ -307,7 +315,8 @@ Valve GG has flow rate=0; tunnels lead to valves FF, HH
 Valve HH has flow rate=22; tunnel leads to valve GG
 Valve II has flow rate=0; tunnels lead to valves AA, JJ
 Valve JJ has flow rate=21; tunnel leads to valve II";
-        assert_eq!(1651, get_max_possible_pressure_released(input).unwrap());
+        let tunnels = parse_tunnels(input).unwrap();
+        assert_eq!(1651, get_max_possible_pressure_released(&tunnels).unwrap());
     }
 
     #[test]
@@ -322,9 +331,10 @@ Valve GG has flow rate=0; tunnels lead to valves FF, HH
 Valve HH has flow rate=22; tunnel leads to valve GG
 Valve II has flow rate=0; tunnels lead to valves AA, JJ
 Valve JJ has flow rate=21; tunnel leads to valve II";
+        let tunnels = parse_tunnels(input).unwrap();
         assert_eq!(
             1707,
-            get_max_possible_pressure_with_elephant(input).unwrap()
+            get_max_possible_pressure_with_elephant(&tunnels).unwrap()
         );
     }
 }
@@ -1,21 +1,29 @@
-use std::{cmp::min, fmt::Display, time::Instant};
+use std::{cmp::min, fmt::Display};
 
 use color_eyre::eyre::{eyre, Result};
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
-
-    let input = include_str!("input/day_17.txt");
-    let directions = parse_directions(input)?;
-    println!(
-        "Part 1: {}",
-        simulate_and_get_highest_rock(2022, &directions)
-    );
-    println!(
-        "Part 2: {}",
-        simulate_and_get_highest_rock(1_000_000_000_000, &directions)
-    );
-    Ok(())
+use crate::{grid::Grid, point::Vec2, solution::Solution};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Vec<Direction>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_directions(input)
+    }
+
+    fn part_one(directions: &Self::Input) -> Result<Self::PartOne> {
+        Ok(simulate_and_get_highest_rock(2022, directions))
+    }
+
+    fn part_two(directions: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(simulate_and_get_highest_rock(1_000_000_000_000, directions))
+    }
 }
 
 fn cycle_check(deltas: &[usize], minimum_length: usize) -> Option<&[usize]> {
@@ -61,7 +69,6 @@ fn parse_directions(input: &str) -> Result<Vec<Direction>> {
     input
         .trim()
         .chars()
-        .into_iter()
         .map(|c| match c {
             '<' => Ok(Direction::Left),
             '>' => Ok(Direction::Right),
@@ -82,7 +89,6 @@ fn simulate(number_of_rocks: usize, directions: &[Direction]) -> Arena {
 
     let mut highest = 0;
     let mut deltas = Vec::new();
-    let start = Instant::now();
 
     for shape in 0..number_of_rocks {
         current_shape = Shape::make_shape(shape % Shape::MAX_SHAPES);
@@ -94,14 +100,14 @@ fn simulate(number_of_rocks: usize, directions: &[Direction]) -> Arena {
             // println!("Moving {current_dir:?}");
             match current_dir {
                 Direction::Left => x_pos = x_pos.saturating_sub(1),
-                Direction::Right => x_pos = min(x_pos + 1, arena.width - current_shape.width),
+                Direction::Right => x_pos = min(x_pos + 1, arena.width() - current_shape.width()),
                 _ => unreachable!(),
             }
             direction_counter = (direction_counter + 1) % directions.len();
             remaining_till_bottom -= 1
         }
 
-        current_shape.lower_left_corner = (x_pos, arena.get_first_empty_row());
+        current_shape.lower_left_corner = Point::new(x_pos, arena.get_first_empty_row());
         // arena.draw_shape(&current_shape);
         // println!("{arena}");
 
@@ -150,15 +156,6 @@ fn simulate(number_of_rocks: usize, directions: &[Direction]) -> Arena {
                 // if shape % 1000 == 0 {
                 //     println!("Reached {shape}");
                 // }
-                if shape == 1_000_000 {
-                    let elapsed = start.elapsed();
-                    println!("Time for 10 million rocks :: {}ms", elapsed.as_millis());
-                    dbg!(&arena.points.len());
-                    println!(
-                        "Time to complete :: {}s",
-                        (elapsed * (number_of_rocks / 1_000_000) as u32).as_secs()
-                    );
-                }
                 break;
             }
         }
@@ -167,11 +164,13 @@ fn simulate(number_of_rocks: usize, directions: &[Direction]) -> Arena {
     arena
 }
 
-type Point = (usize, usize);
+type Point = Vec2<usize>;
+const X_STEP: Point = Point::new(1, 0);
+const Y_STEP: Point = Point::new(0, 1);
 
 struct Arena {
-    width: usize,
-    points: Vec<Cell>,
+    /// with the bottom row first, only as high as the highest rock
+    points: Grid<Cell>,
     highest_empty_row_index: usize,
 }
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -180,33 +179,25 @@ enum Cell {
     Air,
 }
 #[derive(Clone, Copy, Debug)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
     Down,
 }
 struct Shape {
     lower_left_corner: Point,
-    width: usize,
-    height: usize,
-    points: Vec<Cell>,
+    /// with the bottom row first
+    points: Grid<Cell>,
 }
 
 impl Shape {
     const MAX_SHAPES: usize = 5;
     fn make_shape(idx: usize) -> Self {
-        match idx {
-            0 => Shape {
-                lower_left_corner: (0, 0),
-                width: 4,
-                height: 1,
-                points: vec![Cell::Rock; 4],
-            },
-            1 => Shape {
-                lower_left_corner: (0, 0),
-                width: 3,
-                height: 3,
-                points: vec![
+        let (width, points) = match idx {
+            0 => (4, vec![Cell::Rock; 4]),
+            1 => (
+                3,
+                vec![
                     Cell::Air,
                     Cell::Rock,
                     Cell::Air,
@@ -217,12 +208,10 @@ impl Shape {
                     Cell::Rock,
                     Cell::Air,
                 ],
-            },
-            2 => Shape {
-                lower_left_corner: (0, 0),
-                width: 3,
-                height: 3,
-                points: vec![
+            ),
+            2 => (
+                3,
+                vec![
                     Cell::Rock,
                     Cell::Rock,
                     Cell::Rock,
@@ -233,71 +222,76 @@ impl Shape {
                     Cell::Air,
                     Cell::Rock,
                 ],
-            },
-            3 => Shape {
-                lower_left_corner: (0, 0),
-                width: 1,
-                height: 4,
-                points: vec![Cell::Rock; 4],
-            },
-            4 => Shape {
-                lower_left_corner: (0, 0),
-                width: 2,
-                height: 2,
-                points: vec![Cell::Rock; 4],
-            },
+            ),
+            3 => (1, vec![Cell::Rock; 4]),
+            4 => (2, vec![Cell::Rock; 4]),
             _ => unreachable!(),
+        };
+
+        Shape {
+            lower_left_corner: Point::ZERO,
+            points: Grid::from_vec(width, points).expect("shapes are rectangular"),
         }
     }
 
+    fn width(&self) -> usize {
+        self.points.width()
+    }
+
+    fn height(&self) -> usize {
+        self.points.height()
+    }
+
     fn get_point(&self, point: Point) -> Cell {
-        self.points[point.0 as usize + point.1 as usize * self.width]
+        self.points[point.into()]
     }
 }
 impl Arena {
     fn new() -> Self {
         Self {
-            width: 7,
-            points: vec![Cell::Air; 11_000_000],
+            points: Grid::filled(7, 0, Cell::Air),
             highest_empty_row_index: 0,
         }
     }
+
+    fn width(&self) -> usize {
+        self.points.width()
+    }
+
     fn get_point(&self, point: Point) -> Cell {
-        if point.0 >= self.width {
+        if point.x >= self.width() {
             Cell::Rock
-        } else if point.1 >= self.highest_empty_row_index {
-            Cell::Air
         } else {
-            self.points[point.0 + point.1 * self.width]
+            self.points.get(point.into()).copied().unwrap_or(Cell::Air)
         }
     }
 
     fn set_point(&mut self, point: Point, c: Cell) {
-        self.points[point.0 + point.1 * self.width] = c;
+        self.points[point.into()] = c;
     }
     fn row_count(&self) -> usize {
         self.highest_empty_row_index
     }
 
     fn draw_shape(&mut self, s: &Shape) {
-        let required_arena_height = s.lower_left_corner.1 + s.height;
+        let required_arena_height = s.lower_left_corner.y + s.height();
         self.extend_height_with_air(required_arena_height);
 
         // debug_assert_eq!(Cell::Air, self.get_point(s.lower_left_corner));
 
-        for y in 0..s.height {
-            for x in 0..s.width {
+        for y in 0..s.height() {
+            for x in 0..s.width() {
                 // println!(
                 //     "{:?} -> ({}, {})",
                 //     s.get_point((x, y)),
                 //     x + s.lower_left_corner.0,
                 //     y + s.lower_left_corner.1
                 // );
-                let shape_cell = s.get_point((x, y));
+                let shape_cell = s.get_point(Point::new(x, y));
 
                 // no need to draw air
                 if shape_cell == Cell::Rock {
-                    let point_to_draw_to = (x + s.lower_left_corner.0, y + s.lower_left_corner.1);
+                    let point_to_draw_to = Point::new(x, y) + s.lower_left_corner;
                     debug_assert_eq!(Cell::Air, self.get_point(point_to_draw_to));
                     self.set_point(point_to_draw_to, shape_cell);
                 }
@@ -306,8 +300,8 @@ impl Arena {
     }
 
     // fn clear_shape(&mut self, s: &Shape) {
-    //     for y in 0..s.height {
-    //         for x in 0..s.width {
+    //     for y in 0..s.height() {
+    //         for x in 0..s.width() {
     //             let shape_cell = s.get_point((x, y));
     //             if shape_cell == Cell::Rock {
     //                 let point_to_clear = (x + s.lower_left_corner.0, y + s.lower_left_corner.1);
@@ -322,20 +316,20 @@ impl Arena {
     fn move_shape(&mut self, s: &mut Shape, dir: Direction) -> bool {
         match dir {
             Direction::Left => {
-                if (0..s.height)
+                if (0..s.height())
                     .map(|y| {
                         (
-                            (0..s.width)
-                                .find(|x_shape| s.get_point((*x_shape, y)) == Cell::Rock)
+                            (0..s.width())
+                                .find(|x_shape| s.get_point(Point::new(*x_shape, y)) == Cell::Rock)
                                 .unwrap(),
                             y,
                         )
                     })
-                    .map(|(x, y)| (x + s.lower_left_corner.0, y + s.lower_left_corner.1))
-                    .all(|(x, y)| x != 0 && self.get_point((x - 1, y)) == Cell::Air)
+                    .map(|(x, y)| Point::new(x, y) + s.lower_left_corner)
+                    .all(|p| p.x != 0 && self.get_point(p - X_STEP) == Cell::Air)
                 {
                     // self.clear_shape(s);
-                    s.lower_left_corner = (s.lower_left_corner.0 - 1, s.lower_left_corner.1);
+                    s.lower_left_corner -= X_STEP;
                     // self.draw_shape(s);
 
                     true
@@ -344,21 +338,21 @@ impl Arena {
                 }
             }
             Direction::Right => {
-                if (0..s.height)
+                if (0..s.height())
                     .map(|y| {
                         (
-                            (0..s.width)
+                            (0..s.width())
                                 .rev()
-                                .find(|x_shape| s.get_point((*x_shape, y)) == Cell::Rock)
-                                .unwrap_or(s.width),
+                                .find(|x_shape| s.get_point(Point::new(*x_shape, y)) == Cell::Rock)
+                                .unwrap_or(s.width()),
                             y,
                         )
                     })
-                    .map(|(x, y)| (x + s.lower_left_corner.0, y + s.lower_left_corner.1))
-                    .all(|(x, y)| x != self.width - 1 && self.get_point((x + 1, y)) == Cell::Air)
+                    .map(|(x, y)| Point::new(x, y) + s.lower_left_corner)
+                    .all(|p| p.x != self.width() - 1 && self.get_point(p + X_STEP) == Cell::Air)
                 {
                     // self.clear_shape(s);
-                    s.lower_left_corner = (s.lower_left_corner.0 + 1, s.lower_left_corner.1);
+                    s.lower_left_corner += X_STEP;
                     // self.draw_shape(s);
 
                     true
@@ -367,20 +361,22 @@ impl Arena {
                 }
             }
             Direction::Down => {
-                if (0..s.width)
+                if (0..s.width())
                     .map(|x_shape| {
                         (
                             x_shape,
-                            (0..s.height)
-                                .find(|y_shape| s.get_point((x_shape, *y_shape)) == Cell::Rock)
+                            (0..s.height())
+                                .find(|y_shape| {
+                                    s.get_point(Point::new(x_shape, *y_shape)) == Cell::Rock
+                                })
                                 .unwrap(),
                         )
                     })
-                    .map(|(x, y)| (x + s.lower_left_corner.0, y + s.lower_left_corner.1))
-                    .all(|(x, y)| y != 0 && self.get_point((x, y - 1)) == Cell::Air)
+                    .map(|(x, y)| Point::new(x, y) + s.lower_left_corner)
+                    .all(|p| p.y != 0 && self.get_point(p - Y_STEP) == Cell::Air)
                 {
                     // self.clear_shape(s);
-                    s.lower_left_corner = (s.lower_left_corner.0, s.lower_left_corner.1 - 1);
+                    s.lower_left_corner -= Y_STEP;
                     // self.draw_shape(s);
 
                     true
@@ -393,8 +389,7 @@ impl Arena {
 
     fn extend_height_with_air(&mut self, height: usize) {
         if self.highest_empty_row_index < height {
-            (0..((height * self.width).saturating_sub(self.points.len())))
-                .for_each(|_| self.points.push(Cell::Air));
+            self.points.grow_to_height(height, Cell::Air);
             self.highest_empty_row_index = height;
         }
     }
@@ -408,11 +403,11 @@ impl Display for Arena {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for y in (0..=self.row_count()).rev() {
             write!(f, "|")?;
-            for x in 0..self.width {
+            for x in 0..self.width() {
                 write!(
                     f,
                     "{}",
-                    match self.get_point((x, y)) {
+                    match self.get_point(Point::new(x, y)) {
                         Cell::Air => ".",
                         Cell::Rock => "#",
                     }
@@ -430,7 +425,7 @@ impl Display for Arena {
 mod tests {
     use itertools::Itertools;
 
-    use crate::{cycle_check, parse_directions, simulate_and_get_highest_rock};
+    use super::{cycle_check, parse_directions, simulate_and_get_highest_rock};
 
     #[test]
     fn example_part1() {
@@ -1,41 +1,40 @@
-use std::ops::Add;
-
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+use crate::{point::Vec3, solution::Solution};
 
-    let input = include_str!("input/day_18.txt");
+pub struct Day18;
 
-    println!("Part 1: {}", surface_area_of_lava_droplets(input, true)?);
-    println!("Part 2: {}", surface_area_of_lava_droplets(input, false)?);
-    Ok(())
-}
+impl Solution for Day18 {
+    const DAY: u8 = 18;
 
-fn surface_area_of_lava_droplets(input: &str, with_interior: bool) -> Result<i64> {
-    let droplets: Vec<Point> = parse_points(input)?;
+    type Input = Vec<Point>;
+    type PartOne = i64;
+    type PartTwo = i64;
 
-    let directions = [
-        Point::new(1, 0, 0),
-        Point::new(-1, 0, 0),
-        Point::new(0, 1, 0),
-        Point::new(0, -1, 0),
-        Point::new(0, 0, 1),
-        Point::new(0, 0, -1),
-    ];
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_points(input)
+    }
+
+    fn part_one(droplets: &Self::Input) -> Result<Self::PartOne> {
+        Ok(surface_area_of_lava_droplets(droplets, true))
+    }
 
+    fn part_two(droplets: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(surface_area_of_lava_droplets(droplets, false))
+    }
+}
+
+fn surface_area_of_lava_droplets(droplets: &[Point], with_interior: bool) -> i64 {
     let mut outer_air = Vec::new();
 
     if !with_interior {
         let max = 22;
 
-        let init = Point::new(0, 0, 0);
-        outer_air.push(init);
+        outer_air.push(Point::ZERO);
         let mut idx = 0;
         while idx < outer_air.len() {
-            for dir in directions.iter() {
-                let point = outer_air[idx] + *dir;
+            for point in outer_air[idx].neighbours6() {
                 if point.x > max
                     || point.x < -1
                     || point.y > max
@@ -53,13 +52,12 @@ fn surface_area_of_lava_droplets(input: &str, with_interior: bool) -> Result<i64
         }
     }
 
-    Ok(droplets
+    droplets
         .iter()
         .map(|droplet| {
-            directions
-                .iter()
-                .map(|dir| {
-                    let point = *droplet + *dir;
+            droplet
+                .neighbours6()
+                .map(|point| {
                     if droplets.contains(&point) {
                         0
                     } else if with_interior || outer_air.contains(&point) {
@@ -70,7 +68,7 @@ fn surface_area_of_lava_droplets(input: &str, with_interior: bool) -> Result<i64
                 })
                 .sum::<i64>()
         })
-        .sum())
+        .sum()
 }
 
 fn parse_points(input: &str) -> Result<Vec<Point>, color_eyre::Report> {
@@ -87,39 +85,11 @@ fn parse_points(input: &str) -> Result<Vec<Point>, color_eyre::Report> {
         .collect::<Result<Vec<_>>>()
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Point {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-impl Point {
-    fn new(x: i64, y: i64, z: i64) -> Self {
-        Point { x, y, z }
-    }
-}
-impl std::fmt::Display for Point {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {}, {})", self.x, self.y, self.z)?;
-        Ok(())
-    }
-}
-
-impl Add for Point {
-    type Output = Point;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Point {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
-        }
-    }
-}
+pub type Point = Vec3<i64>;
 
 #[cfg(test)]
 mod tests {
-    use crate::surface_area_of_lava_droplets;
+    use super::{parse_points, surface_area_of_lava_droplets};
 
     #[test]
     fn example_part1() {
@@ -136,7 +106,8 @@ mod tests {
 3,2,5
 2,1,5
 2,3,5";
-        assert_eq!(64, surface_area_of_lava_droplets(input, true).unwrap());
+        let droplets = parse_points(input).unwrap();
+        assert_eq!(64, surface_area_of_lava_droplets(&droplets, true));
     }
 
     #[test]
@@ -154,6 +125,7 @@ mod tests {
 3,2,5
 2,1,5
 2,3,5";
-        assert_eq!(58, surface_area_of_lava_droplets(input, false).unwrap());
+        let droplets = parse_points(input).unwrap();
+        assert_eq!(58, surface_area_of_lava_droplets(&droplets, false));
     }
 }
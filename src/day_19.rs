@@ -3,27 +3,36 @@ use std::collections::HashMap;
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+use crate::solution::Solution;
 
-    let input = include_str!("input/day_19.txt");
+pub struct Day19;
 
-    println!("Part 1: {}", sum_of_quality_levels(input)?);
-    println!("Part 2: {}", product_of_max(input, 3)?);
-    Ok(())
-}
+impl Solution for Day19 {
+    const DAY: u8 = 19;
 
-fn sum_of_quality_levels(input: &str) -> Result<u64> {
-    let blueprints = input
-        .lines()
-        .map(parse_blueprint)
-        .collect::<Result<Vec<_>>>()?;
+    type Input = Vec<Blueprint>;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    Ok(blueprints
-        .into_iter()
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_blueprints(input)
+    }
+
+    fn part_one(blueprints: &Self::Input) -> Result<Self::PartOne> {
+        Ok(sum_of_quality_levels(blueprints))
+    }
+
+    fn part_two(blueprints: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(product_of_max(blueprints, 3))
+    }
+}
+
+fn sum_of_quality_levels(blueprints: &[Blueprint]) -> u64 {
+    blueprints
+        .iter()
         .enumerate()
-        .map(|(i, blueprint)| quality_level(i + 1, &blueprint))
-        .sum())
+        .map(|(i, blueprint)| quality_level(i + 1, blueprint))
+        .sum()
 }
 
 fn max_geodes_from_blueprint(blueprint: &Blueprint, time: u32) -> u32 {
@@ -110,11 +119,12 @@ fn max_possible_geodes_optimized(
     }
 }
 
+#[allow(dead_code)]
 fn max_possible_geodes(
     cache: &mut HashMap<(u32, Robots, Store), u32>,
     blueprint: &Blueprint,
     mut current_time: u32,
-    mut current_bots: Robots,
+    current_bots: Robots,
     mut current_store: Store,
 ) -> (u32, u64) {
     if current_time == 0 {
@@ -227,7 +237,7 @@ fn quality_level(blueprint_number: usize, blueprint: &Blueprint) -> u64 {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Blueprint {
+pub struct Blueprint {
     ore: u32,
     clay: u32,
     obsidian: (u32, u32),
@@ -250,6 +260,7 @@ enum RobotType {
 }
 
 impl Robots {
+    #[allow(dead_code)]
     fn new_with_increased(&self, ore: u32, clay: u32, obsidian: u32, geode: u32) -> Robots {
         Self {
             ore: self.ore + ore,
@@ -300,6 +311,7 @@ impl Store {
         self.geode += current_bots.geode;
     }
 
+    #[allow(dead_code)]
     fn new_with_reduced(&self, ore: u32, clay: u32, obsidian: u32, geode: u32) -> Self {
         Self {
             ore: self.ore - ore,
@@ -380,21 +392,23 @@ fn parse_blueprint(line: &str) -> Result<Blueprint> {
     })
 }
 
-fn product_of_max(input: &str, remaining: usize) -> Result<u64> {
-    Ok(input
-        .lines()
+fn parse_blueprints(input: &str) -> Result<Vec<Blueprint>> {
+    input.lines().map(parse_blueprint).collect()
+}
+
+fn product_of_max(blueprints: &[Blueprint], remaining: usize) -> u64 {
+    blueprints
+        .iter()
         .take(remaining)
-        .map(|l| parse_blueprint(l).map(|b| max_geodes_from_blueprint(&b, 32) as u64))
-        .collect::<Result<Vec<_>>>()?
-        .into_iter()
-        .product())
+        .map(|b| max_geodes_from_blueprint(b, 32) as u64)
+        .product()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        max_geodes_from_blueprint, parse_blueprint, product_of_max, sum_of_quality_levels,
-        Blueprint,
+    use super::{
+        max_geodes_from_blueprint, parse_blueprint, parse_blueprints, product_of_max,
+        sum_of_quality_levels, Blueprint,
     };
 
     #[test]
@@ -440,7 +454,7 @@ mod tests {
         let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
 Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
 
-        assert_eq!(33, sum_of_quality_levels(input).unwrap());
+        assert_eq!(33, sum_of_quality_levels(&parse_blueprints(input).unwrap()));
     }
 
     #[test]
@@ -448,6 +462,9 @@ Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsid
         let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
 Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
 
-        assert_eq!(56 * 62, product_of_max(input, 2).unwrap());
+        assert_eq!(
+            56 * 62,
+            product_of_max(&parse_blueprints(input).unwrap(), 2)
+        );
     }
 }
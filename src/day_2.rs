@@ -1,141 +1,123 @@
-fn main() {
-    let input = include_str!("input/day_2.txt");
-    println!("Part 1: {}", score_part1(input));
-    println!("Part 2: {}", score_part2(input));
-}
+use color_eyre::eyre::{eyre, Result, WrapErr};
 
-fn score_part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|t| {
-            let pair = t.split_once(' ');
-
-            match pair {
-                Some((opponent, player)) => {
-                    let player = RPS::from_player(player);
-                    let opponent = RPS::from_opponent(opponent);
-
-                    GameResult::calculate_score(player, opponent)
-                }
-                None => 0,
-            }
-        })
-        .sum()
-}
+use crate::solution::Solution;
 
-fn score_part2(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|t| {
-            let pair = t.split_once(' ');
-
-            let (opponent, result) = pair.unwrap();
-            let opponent = RPS::from_opponent(opponent);
-            let result = GameResult::from_input(result);
-            let player_selected = match (&opponent, result) {
-                (RPS::Rock, GameResult::Draw) => RPS::Rock,
-                (RPS::Paper, GameResult::Draw) => RPS::Paper,
-                (RPS::Scissors, GameResult::Draw) => RPS::Scissors,
-
-                (RPS::Rock, GameResult::Won) => RPS::Paper,
-                (RPS::Paper, GameResult::Won) => RPS::Scissors,
-                (RPS::Scissors, GameResult::Won) => RPS::Rock,
-
-                (RPS::Scissors, GameResult::Lost) => RPS::Paper,
-                (RPS::Rock, GameResult::Lost) => RPS::Scissors,
-                (RPS::Paper, GameResult::Lost) => RPS::Rock,
-            };
-
-            GameResult::calculate_score(player_selected, opponent)
-        })
-        .sum()
-}
+use self::rules::{Rules, Strategy};
 
-#[derive(PartialEq, Eq)]
-enum RPS {
-    Rock,
-    Paper,
-    Scissors,
-}
-enum GameResult {
-    Won,
-    Lost,
-    Draw,
-}
+pub mod optimizer;
+pub mod rules;
 
-impl GameResult {
-    fn from_input(s: &str) -> Self {
-        match s {
-            "X" => Self::Lost,
-            "Y" => Self::Draw,
-            _ => Self::Won,
-        }
-    }
+pub struct Day2;
 
-    fn calculate_score(player: RPS, opponent: RPS) -> u32 {
-        let mut score = match player {
-            RPS::Rock => 1,
-            RPS::Paper => 2,
-            RPS::Scissors => 3,
-        };
+impl Solution for Day2 {
+    const DAY: u8 = 2;
 
-        score += match RPS::did_win(opponent, player) {
-            GameResult::Won => 6,
-            GameResult::Lost => 0,
-            GameResult::Draw => 3,
-        };
+    type Input = Vec<Round>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-        score
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_guide(input)
     }
-}
 
-impl RPS {
-    fn from_player(player: &str) -> Self {
-        match player {
-            "X" => RPS::Rock,
-            "Y" => RPS::Paper,
-            _ => RPS::Scissors,
-        }
+    fn part_one(guide: &Self::Input) -> Result<Self::PartOne> {
+        score_guide(&Rules::rock_paper_scissors(), guide, Strategy::Move)
     }
-    fn from_opponent(opponent: &str) -> Self {
-        match opponent {
-            "A" => RPS::Rock,
-            "B" => RPS::Paper,
-            _ => RPS::Scissors,
-        }
+
+    fn part_two(guide: &Self::Input) -> Result<Self::PartTwo> {
+        score_guide(&Rules::rock_paper_scissors(), guide, Strategy::Outcome)
     }
+}
 
-    fn did_win(opponent: RPS, player: RPS) -> GameResult {
-        match (opponent, player) {
-            (RPS::Rock, RPS::Rock) => GameResult::Draw,
-            (RPS::Paper, RPS::Paper) => GameResult::Draw,
-            (RPS::Scissors, RPS::Scissors) => GameResult::Draw,
+/// A line of the strategy guide, with the symbols still to be interpreted by the [`Rules`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub line: usize,
+    pub opponent: String,
+    pub response: String,
+}
 
-            (RPS::Rock, RPS::Paper) => GameResult::Won,
-            (RPS::Paper, RPS::Scissors) => GameResult::Won,
-            (RPS::Scissors, RPS::Rock) => GameResult::Won,
+pub fn parse_guide(input: &str) -> Result<Vec<Round>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(
+            |(i, l)| match l.split_whitespace().collect::<Vec<_>>()[..] {
+                [opponent, response] => Ok(Round {
+                    line: i + 1,
+                    opponent: opponent.to_string(),
+                    response: response.to_string(),
+                }),
+                _ => Err(eyre!("line {}: expected two columns, got {l:?}", i + 1)),
+            },
+        )
+        .collect()
+}
 
-            (RPS::Rock, RPS::Scissors) => GameResult::Lost,
-            (RPS::Paper, RPS::Rock) => GameResult::Lost,
-            (RPS::Scissors, RPS::Paper) => GameResult::Lost,
-        }
-    }
+pub fn score_guide(rules: &Rules, guide: &[Round], strategy: Strategy) -> Result<u32> {
+    guide
+        .iter()
+        .map(|round| {
+            rules
+                .score_round(&round.opponent, &round.response, strategy)
+                .wrap_err_with(|| format!("line {}", round.line))
+        })
+        .sum()
 }
 
-#[test]
-fn example_test_part1() {
-    let input = "A Y
+#[cfg(test)]
+mod tests {
+    use super::{parse_guide, rules::Rules, score_guide, Strategy};
+
+    const EXAMPLE: &str = "A Y
 B X
 C Z";
 
-    assert_eq!(score_part1(input), 15);
-}
+    #[test]
+    fn example_part1() {
+        let guide = parse_guide(EXAMPLE).unwrap();
+        assert_eq!(
+            15,
+            score_guide(&Rules::rock_paper_scissors(), &guide, Strategy::Move).unwrap()
+        );
+    }
 
-#[test]
-fn example_test_part2() {
-    let input = "A Y
-B X
-C Z";
+    #[test]
+    fn example_part2() {
+        let guide = parse_guide(EXAMPLE).unwrap();
+        assert_eq!(
+            12,
+            score_guide(&Rules::rock_paper_scissors(), &guide, Strategy::Outcome).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_guide_test() {
+        assert!(parse_guide("A Y\nB\n").is_err());
+
+        let guide = parse_guide("A Y\n\nB Q\n").unwrap();
+        let error = score_guide(&Rules::rock_paper_scissors(), &guide, Strategy::Move).unwrap_err();
+        assert_eq!("line 3", error.to_string());
+        assert_eq!(
+            "Unknown player symbol \"Q\"",
+            error.root_cause().to_string()
+        );
+    }
 
-    assert_eq!(score_part2(input), 12);
+    #[test]
+    fn lizard_spock_guide_test() {
+        let guide = parse_guide("A Z\nE Y\nC X").unwrap();
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        // Scissors lose to Rock, Lizard loses to Scissors, Paper draws
+        assert_eq!(
+            5 + 4 + (3 + 3),
+            score_guide(&rules, &guide, Strategy::Move).unwrap()
+        );
+        // beat Rock with Paper (over Spock), draw Scissors, lose to Paper with Spock (over Rock)
+        assert_eq!(
+            (3 + 6) + (5 + 3) + 2,
+            score_guide(&rules, &guide, Strategy::Outcome).unwrap()
+        );
+    }
 }
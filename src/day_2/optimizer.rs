@@ -0,0 +1,254 @@
+use color_eyre::eyre::{eyre, Result};
+use itertools::Itertools;
+
+use super::{
+    rules::{Outcome, Rules, Strategy},
+    score_guide, Round,
+};
+
+/// The highest score possible against the opponent moves of `guide`, i.e. always winning with the
+/// best scoring move
+pub fn best_score(rules: &Rules, guide: &[Round]) -> Result<u32> {
+    guide
+        .iter()
+        .map(|round| {
+            let opponent = rules.opponent_move(&round.opponent)?;
+            Ok((0..rules.moves().len())
+                .map(|player| rules.score(opponent, player))
+                .max()
+                .unwrap_or(0))
+        })
+        .sum()
+}
+
+/// Score of the guide when its second column is read as moves with one of the possible
+/// assignments of symbols to moves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappingScore {
+    /// The move each player symbol stands for, in the order of the rules' player symbols
+    pub moves: Vec<String>,
+    pub score: u32,
+}
+
+/// Scores the guide under every permutation of the player symbols, best first
+pub fn mapping_scores(rules: &Rules, guide: &[Round]) -> Result<Vec<MappingScore>> {
+    let symbols = rules.player_symbols();
+    if symbols.is_empty() {
+        return Err(eyre!("The rules have no player symbols to permute"));
+    }
+
+    let mut scores = (0..symbols.len())
+        .permutations(symbols.len())
+        .map(|permutation| {
+            // symbol `i` stands for move `permutation[i]`
+            let mut mapped = vec![""; symbols.len()];
+            for (symbol, played) in symbols.iter().zip(&permutation) {
+                mapped[*played] = symbol;
+            }
+            let rules = rules.clone().with_player_symbols(&mapped)?;
+            Ok(MappingScore {
+                moves: permutation
+                    .iter()
+                    .map(|m| rules.move_name(*m).to_string())
+                    .collect(),
+                score: score_guide(&rules, guide, Strategy::Move)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    scores.sort_by_key(|s| std::cmp::Reverse(s.score));
+    Ok(scores)
+}
+
+/// Picks the opponent's move for each round of a simulated game
+pub trait OpponentModel {
+    fn next_move(&mut self, round: usize, moves: usize) -> usize;
+}
+
+/// Plays uniformly random moves, reproducibly for the same seed
+pub struct RandomOpponent(fastrand::Rng);
+
+impl RandomOpponent {
+    pub fn new(seed: u64) -> Self {
+        RandomOpponent(fastrand::Rng::with_seed(seed))
+    }
+}
+
+impl OpponentModel for RandomOpponent {
+    fn next_move(&mut self, _round: usize, moves: usize) -> usize {
+        self.0.usize(..moves)
+    }
+}
+
+/// Repeats a fixed sequence of moves
+pub struct PatternOpponent(Vec<usize>);
+
+impl PatternOpponent {
+    pub fn new(pattern: Vec<usize>) -> Result<Self> {
+        if pattern.is_empty() {
+            return Err(eyre!("The opponent's pattern needs at least one move"));
+        }
+        Ok(PatternOpponent(pattern))
+    }
+
+    /// A pattern of opponent symbols, like `A,C,C`
+    pub fn parse(rules: &Rules, pattern: &str) -> Result<Self> {
+        PatternOpponent::new(
+            pattern
+                .split(',')
+                .map(|symbol| rules.opponent_move(symbol.trim()))
+                .collect::<Result<_>>()?,
+        )
+    }
+}
+
+impl OpponentModel for PatternOpponent {
+    fn next_move(&mut self, round: usize, _moves: usize) -> usize {
+        self.0[round % self.0.len()]
+    }
+}
+
+/// Results of playing the guide a number of times
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Simulation {
+    pub games: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub min_score: u32,
+    pub max_score: u32,
+    pub total_score: u64,
+}
+
+impl Simulation {
+    pub fn rounds(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    pub fn mean_score(&self) -> f64 {
+        self.total_score as f64 / self.games.max(1) as f64
+    }
+}
+
+/// Plays the second column of `guide` `games` times against moves picked by `opponent`,
+/// ignoring the guide's first column other than for the number of rounds
+pub fn simulate(
+    rules: &Rules,
+    guide: &[Round],
+    strategy: Strategy,
+    opponent: &mut dyn OpponentModel,
+    games: usize,
+) -> Result<Simulation> {
+    let mut simulation = Simulation {
+        min_score: u32::MAX,
+        ..Simulation::default()
+    };
+    for _ in 0..games {
+        let mut score = 0;
+        for (i, round) in guide.iter().enumerate() {
+            let opponent = opponent.next_move(i, rules.moves().len());
+            let player = rules.player_move(opponent, &round.response, strategy)?;
+            match rules.outcome(opponent, player) {
+                Outcome::Win => simulation.wins += 1,
+                Outcome::Draw => simulation.draws += 1,
+                Outcome::Loss => simulation.losses += 1,
+            }
+            score += rules.score(opponent, player);
+        }
+        simulation.games += 1;
+        simulation.min_score = simulation.min_score.min(score);
+        simulation.max_score = simulation.max_score.max(score);
+        simulation.total_score += score as u64;
+    }
+    if simulation.games == 0 {
+        simulation.min_score = 0;
+    }
+    Ok(simulation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        best_score, mapping_scores, simulate, PatternOpponent, RandomOpponent, Simulation,
+    };
+    use crate::day_2::{
+        parse_guide,
+        rules::{Rules, Strategy},
+    };
+
+    const EXAMPLE: &str = "A Y
+B X
+C Z";
+
+    #[test]
+    fn best_score_test() {
+        let guide = parse_guide(EXAMPLE).unwrap();
+        // Paper, Scissors then Rock, all wins
+        assert_eq!(
+            8 + 9 + 7,
+            best_score(&Rules::rock_paper_scissors(), &guide).unwrap()
+        );
+    }
+
+    #[test]
+    fn mapping_scores_test() {
+        let guide = parse_guide(EXAMPLE).unwrap();
+        let scores = mapping_scores(&Rules::rock_paper_scissors(), &guide).unwrap();
+        assert_eq!(6, scores.len());
+        assert_eq!(
+            vec!["Scissors", "Paper", "Rock"],
+            scores[0].moves,
+            "X as Scissors, Y as Paper, Z as Rock"
+        );
+        assert_eq!(24, scores[0].score);
+        // the puzzle's own mapping
+        assert!(scores
+            .iter()
+            .any(|s| s.moves == ["Rock", "Paper", "Scissors"] && s.score == 15));
+        assert!(scores.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn simulate_test() {
+        let rules = Rules::rock_paper_scissors();
+        let guide = parse_guide(EXAMPLE).unwrap();
+
+        // replaying the guide's own opponent gives the guide's score
+        let mut opponent = PatternOpponent::parse(&rules, "A, B, C").unwrap();
+        let simulation = simulate(&rules, &guide, Strategy::Move, &mut opponent, 2).unwrap();
+        assert_eq!(
+            Simulation {
+                games: 2,
+                wins: 2,
+                draws: 2,
+                losses: 2,
+                min_score: 15,
+                max_score: 15,
+                total_score: 30
+            },
+            simulation
+        );
+
+        let mut opponent = PatternOpponent::parse(&rules, "A").unwrap();
+        let simulation = simulate(&rules, &guide, Strategy::Outcome, &mut opponent, 1).unwrap();
+        assert_eq!(
+            (1, 1, 1),
+            (simulation.wins, simulation.draws, simulation.losses)
+        );
+        assert!(PatternOpponent::parse(&rules, "A,Q").is_err());
+
+        let random = |seed| {
+            simulate(
+                &rules,
+                &guide,
+                Strategy::Move,
+                &mut RandomOpponent::new(seed),
+                100,
+            )
+            .unwrap()
+        };
+        let simulation = random(7);
+        assert_eq!(300, simulation.rounds());
+        assert!(simulation.min_score <= simulation.max_score);
+        assert_eq!(simulation, random(7));
+    }
+}
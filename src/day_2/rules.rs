@@ -0,0 +1,283 @@
+use std::fmt::Display;
+
+use color_eyre::eyre::{eyre, Result};
+
+/// How a round ended, for the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Loss => write!(f, "loss"),
+            Outcome::Draw => write!(f, "draw"),
+            Outcome::Win => write!(f, "win"),
+        }
+    }
+}
+
+/// How the second column of a strategy guide is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// The move to play
+    Move,
+    /// The outcome to aim for, the move is picked to get it
+    Outcome,
+}
+
+/// A cyclic hand game: every move beats the `(N - 1) / 2` moves before it in the list (wrapping
+/// around) and loses to the ones after it. Rock-Paper-Scissors is `[Rock, Paper, Scissors]`,
+/// Rock-Paper-Scissors-Lizard-Spock is `[Rock, Spock, Paper, Lizard, Scissors]`.
+///
+/// Moves are referred to by their index in the list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    moves: Vec<String>,
+    move_scores: Vec<u32>,
+    /// Indexed by [`Outcome`]
+    outcome_scores: [u32; 3],
+    opponent_symbols: Vec<String>,
+    player_symbols: Vec<String>,
+    /// Indexed by [`Outcome`]
+    outcome_symbols: [String; 3],
+}
+
+impl Rules {
+    /// Rules for `moves` scoring `1..=N` for the move played and 0/3/6 for a loss/draw/win,
+    /// with no symbols assigned yet
+    pub fn new(moves: &[&str]) -> Result<Self> {
+        if moves.len() < 3 || moves.len().is_multiple_of(2) {
+            return Err(eyre!(
+                "A cyclic game needs an odd number of at least 3 moves, got {}",
+                moves.len()
+            ));
+        }
+        Ok(Rules {
+            moves: moves.iter().map(|m| m.to_string()).collect(),
+            move_scores: (1..=moves.len() as u32).collect(),
+            outcome_scores: [0, 3, 6],
+            opponent_symbols: Vec::new(),
+            player_symbols: Vec::new(),
+            outcome_symbols: Default::default(),
+        })
+    }
+
+    /// The rules of the puzzle: `A`/`B`/`C` and `X`/`Y`/`Z` for Rock/Paper/Scissors, or `X`/`Y`/`Z`
+    /// to lose/draw/win
+    pub fn rock_paper_scissors() -> Self {
+        Rules::new(&["Rock", "Paper", "Scissors"])
+            .and_then(|rules| rules.with_opponent_symbols(&["A", "B", "C"]))
+            .and_then(|rules| rules.with_player_symbols(&["X", "Y", "Z"]))
+            .map(|rules| rules.with_outcome_symbols(["X", "Y", "Z"]))
+            .expect("Rock-Paper-Scissors rules are valid")
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Rules::new(&["Rock", "Spock", "Paper", "Lizard", "Scissors"])
+            .and_then(|rules| rules.with_opponent_symbols(&["A", "B", "C", "D", "E"]))
+            .and_then(|rules| rules.with_player_symbols(&["V", "W", "X", "Y", "Z"]))
+            .map(|rules| rules.with_outcome_symbols(["X", "Y", "Z"]))
+            .expect("Rock-Paper-Scissors-Lizard-Spock rules are valid")
+    }
+
+    /// Score for playing each move, in the order of the moves
+    pub fn with_move_scores(mut self, scores: &[u32]) -> Result<Self> {
+        self.check_per_move("move scores", scores.len())?;
+        self.move_scores = scores.to_vec();
+        Ok(self)
+    }
+
+    /// Score for a loss, draw and win
+    pub fn with_outcome_scores(mut self, scores: [u32; 3]) -> Self {
+        self.outcome_scores = scores;
+        self
+    }
+
+    /// Symbols of the opponent's moves in the first column, in the order of the moves
+    pub fn with_opponent_symbols(mut self, symbols: &[&str]) -> Result<Self> {
+        self.check_per_move("opponent symbols", symbols.len())?;
+        self.opponent_symbols = symbols.iter().map(|s| s.to_string()).collect();
+        Ok(self)
+    }
+
+    /// Symbols of the player's moves in the second column with [`Strategy::Move`]
+    pub fn with_player_symbols(mut self, symbols: &[&str]) -> Result<Self> {
+        self.check_per_move("player symbols", symbols.len())?;
+        self.player_symbols = symbols.iter().map(|s| s.to_string()).collect();
+        Ok(self)
+    }
+
+    /// Symbols for a loss, draw and win in the second column with [`Strategy::Outcome`]
+    pub fn with_outcome_symbols(mut self, symbols: [&str; 3]) -> Self {
+        self.outcome_symbols = symbols.map(str::to_string);
+        self
+    }
+
+    fn check_per_move(&self, what: &str, len: usize) -> Result<()> {
+        if len != self.moves.len() {
+            return Err(eyre!(
+                "Expected {} {what}, one per move, got {len}",
+                self.moves.len()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn moves(&self) -> &[String] {
+        &self.moves
+    }
+
+    pub fn move_name(&self, index: usize) -> &str {
+        &self.moves[index]
+    }
+
+    pub fn opponent_symbols(&self) -> &[String] {
+        &self.opponent_symbols
+    }
+
+    pub fn player_symbols(&self) -> &[String] {
+        &self.player_symbols
+    }
+
+    pub fn outcome(&self, opponent: usize, player: usize) -> Outcome {
+        let n = self.moves.len();
+        match (player + n - opponent) % n {
+            0 => Outcome::Draw,
+            d if d <= n / 2 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    pub fn score(&self, opponent: usize, player: usize) -> u32 {
+        self.move_scores[player] + self.outcome_scores[self.outcome(opponent, player).index()]
+    }
+
+    /// The move getting `outcome` against `opponent`, the highest scoring one if there are several
+    pub fn move_for(&self, opponent: usize, outcome: Outcome) -> usize {
+        (0..self.moves.len())
+            .filter(|player| self.outcome(opponent, *player) == outcome)
+            .rev()
+            .max_by_key(|player| self.move_scores[*player])
+            .expect("every outcome is possible against any move")
+    }
+
+    pub fn opponent_move(&self, symbol: &str) -> Result<usize> {
+        self.opponent_symbols
+            .iter()
+            .position(|s| s == symbol)
+            .ok_or_else(|| eyre!("Unknown opponent symbol {symbol:?}"))
+    }
+
+    /// The move played for the second column of a round
+    pub fn player_move(&self, opponent: usize, symbol: &str, strategy: Strategy) -> Result<usize> {
+        match strategy {
+            Strategy::Move => self
+                .player_symbols
+                .iter()
+                .position(|s| s == symbol)
+                .ok_or_else(|| eyre!("Unknown player symbol {symbol:?}")),
+            Strategy::Outcome => {
+                let outcome = self
+                    .outcome_symbols
+                    .iter()
+                    .position(|s| s == symbol)
+                    .map(|i| Outcome::ALL[i])
+                    .ok_or_else(|| eyre!("Unknown outcome symbol {symbol:?}"))?;
+                Ok(self.move_for(opponent, outcome))
+            }
+        }
+    }
+
+    /// Score of a round of a strategy guide
+    pub fn score_round(&self, opponent: &str, response: &str, strategy: Strategy) -> Result<u32> {
+        let opponent = self.opponent_move(opponent)?;
+        let player = self.player_move(opponent, response, strategy)?;
+        Ok(self.score(opponent, player))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Outcome, Rules, Strategy};
+
+    #[test]
+    fn rock_paper_scissors_test() {
+        let rules = Rules::rock_paper_scissors();
+        // paper beats rock, rock beats scissors, scissors beat paper
+        assert_eq!(Outcome::Win, rules.outcome(0, 1));
+        assert_eq!(Outcome::Loss, rules.outcome(0, 2));
+        assert_eq!(Outcome::Win, rules.outcome(1, 2));
+        assert_eq!(Outcome::Draw, rules.outcome(2, 2));
+        assert_eq!(8, rules.score(0, 1));
+        assert_eq!(1, rules.score(1, 0));
+
+        assert_eq!(1, rules.move_for(1, Outcome::Draw));
+        assert_eq!(0, rules.move_for(2, Outcome::Win));
+        assert_eq!(1, rules.move_for(2, Outcome::Loss));
+
+        assert_eq!(8, rules.score_round("A", "Y", Strategy::Move).unwrap());
+        assert_eq!(4, rules.score_round("A", "Y", Strategy::Outcome).unwrap());
+        assert!(rules.score_round("D", "Y", Strategy::Move).is_err());
+        assert!(rules.score_round("A", "W", Strategy::Outcome).is_err());
+    }
+
+    #[test]
+    fn lizard_spock_test() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let index = |name: &str| rules.moves().iter().position(|m| m == name).unwrap();
+        for (winner, loser) in [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ] {
+            assert_eq!(Outcome::Win, rules.outcome(index(loser), index(winner)));
+            assert_eq!(Outcome::Loss, rules.outcome(index(winner), index(loser)));
+        }
+
+        // both Paper and Spock beat Rock, Paper scores more
+        assert_eq!(
+            "Paper",
+            rules.move_name(rules.move_for(index("Rock"), Outcome::Win))
+        );
+    }
+
+    #[test]
+    fn configuration_test() {
+        assert!(Rules::new(&["Rock", "Paper"]).is_err());
+        assert!(Rules::new(&["A", "B", "C", "D"]).is_err());
+
+        let rules = Rules::new(&["Rock", "Paper", "Scissors"])
+            .unwrap()
+            .with_move_scores(&[10, 20, 30])
+            .unwrap()
+            .with_outcome_scores([1, 2, 3])
+            .with_opponent_symbols(&["r", "p", "s"])
+            .unwrap()
+            .with_outcome_symbols(["lose", "draw", "win"]);
+        assert_eq!(
+            33,
+            rules.score_round("p", "win", Strategy::Outcome).unwrap()
+        );
+        assert!(rules.score_round("p", "X", Strategy::Move).is_err());
+        assert!(rules.clone().with_player_symbols(&["X"]).is_err());
+    }
+}
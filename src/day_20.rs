@@ -1,35 +1,47 @@
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+use crate::solution::Solution;
 
-    let input = include_str!("input/day_20.txt");
-    // println!("Part 1: {}", visible_tree_count(input)?);
-    // println!("Part 2: {}", highest_scenic_score(input)?);
-    Ok(())
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Vec<i32>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| l.parse::<i32>())
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn part_one(_data: &Self::Input) -> Result<Self::PartOne> {
+        Err(eyre!("Day 20 is not solved yet"))
+    }
+
+    fn part_two(_data: &Self::Input) -> Result<Self::PartTwo> {
+        Err(eyre!("Day 20 is not solved yet"))
+    }
 }
 
-fn mix_data(data: &[i32]) -> Vec<i32> {
-    let mut to_mix: Vec<i32> = dbg!(data.to_vec());
+pub fn mix_data(data: &[i32]) -> Vec<i32> {
+    let mut to_mix: Vec<i32> = data.to_vec();
 
     for num in data {
         let (idx, _) = to_mix.iter().find_position(|n| *n == num).unwrap();
-        let mut idx = idx as i32;
-
-        for _ in 0..num.abs() {
-            let next_idx = idx + num.signum() % data.len() as i32;
-            to_mix.swap(
-                idx as usize,
-                if next_idx < 0 {
-                    data.len() next_idx
-                } else {
-                    next_idx
-                },
-            );
-            idx = next_idx;
-        }
-        println!("After mixing {} : {:?}", num, to_mix);
+        let item = to_mix.remove(idx);
+
+        // moving to the front of the circle is the same as moving to the back
+        let next_idx = match (idx as i32 + num).rem_euclid(to_mix.len() as i32) as usize {
+            0 => to_mix.len(),
+            next_idx => next_idx,
+        };
+        to_mix.insert(next_idx, item);
     }
 
     to_mix
@@ -37,7 +49,7 @@ fn mix_data(data: &[i32]) -> Vec<i32> {
 
 #[cfg(test)]
 mod tests {
-    use crate::mix_data;
+    use super::mix_data;
     #[test]
     fn mix_test() {
         let input = [1, 2, -3, 3, -2, 0, 4];
@@ -1,101 +1,360 @@
-fn main() {
-    println!("Day 3");
-    let input = include_str!("input/day_3.txt");
-    println!("Part 1: {}", sum_of_common_priorities(input));
-    println!("Part 2: {}", sum_of_elf_group_priorities(input));
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitOr, Sub},
+};
+
+use color_eyre::eyre::{eyre, Result};
+
+use crate::solution::Solution;
+
+pub mod badges;
+
+pub struct Day3;
+
+/// Number of elves sharing a badge in part 2
+pub const GROUP_SIZE: usize = 3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Rucksack>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_rucksacks(input)
+    }
+
+    fn part_one(rucksacks: &Self::Input) -> Result<Self::PartOne> {
+        sum_of_common_priorities(rucksacks)
+    }
+
+    fn part_two(rucksacks: &Self::Input) -> Result<Self::PartTwo> {
+        sum_of_elf_group_priorities(rucksacks, GROUP_SIZE)
+    }
 }
 
-fn sum_of_common_priorities(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let (first, second) = line.split_at(line.len() / 2);
-            let common = find_common_for_2(first, second).unwrap();
-            char_priority(common)
+fn sum_of_common_priorities(rucksacks: &[Rucksack]) -> Result<u32> {
+    rucksacks
+        .iter()
+        .map(|rucksack| {
+            let common = rucksack.common().single().ok_or_else(|| {
+                eyre!(
+                    "line {}: compartments share {}, expected exactly one item",
+                    rucksack.line,
+                    rucksack.common()
+                )
+            })?;
+            Ok(char_priority(common).unwrap_or_default())
         })
         .sum()
 }
 
-fn sum_of_elf_group_priorities(input: &str) -> u32 {
-    let lines = input.lines().collect::<Vec<&str>>();
+fn sum_of_elf_group_priorities(rucksacks: &[Rucksack], size: usize) -> Result<u32> {
+    groups(rucksacks, size)?
+        .map(|group| Ok(char_priority(badge(group)?).unwrap_or_default()))
+        .sum()
+}
+
+/// Consecutive groups of `size` rucksacks, failing if the last group would be incomplete
+pub fn groups(rucksacks: &[Rucksack], size: usize) -> Result<std::slice::Chunks<'_, Rucksack>> {
+    if size == 0 {
+        return Err(eyre!("Groups need at least one elf"));
+    }
+    let left_over = rucksacks.len() % size;
+    if left_over != 0 {
+        let first = &rucksacks[rucksacks.len() - left_over];
+        return Err(eyre!(
+            "line {}: incomplete group, {left_over} rucksacks left over after groups of {size}",
+            first.line
+        ));
+    }
+    Ok(rucksacks.chunks(size))
+}
+
+/// The only item carried by every elf of the group
+pub fn badge(group: &[Rucksack]) -> Result<char> {
+    let common = common_items(group);
+    common.single().ok_or_else(|| {
+        eyre!(
+            "lines {}: group shares {common}, expected exactly one badge",
+            group
+                .iter()
+                .map(|r| r.line.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })
+}
+
+/// Items carried by every elf of the group
+pub fn common_items(group: &[Rucksack]) -> ItemSet {
+    group
+        .iter()
+        .map(Rucksack::items)
+        .reduce(|a, b| a & b)
+        .unwrap_or_default()
+}
+
+/// A rucksack from the input, with the items of each of its compartments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    pub line: usize,
+    pub first: ItemSet,
+    pub second: ItemSet,
+}
+
+impl Rucksack {
+    pub fn items(&self) -> ItemSet {
+        self.first | self.second
+    }
 
-    let mut total = 0;
-    for i in (0..lines.len()).step_by(3) {
-        let common = find_common_for_3(lines[i], lines[i + 1], lines[i + 2]).unwrap();
-        total += char_priority(common)
+    /// Items in both compartments
+    pub fn common(&self) -> ItemSet {
+        self.first & self.second
     }
+}
 
-    total
+pub fn parse_rucksacks(input: &str) -> Result<Vec<Rucksack>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| parse_rucksack(i + 1, line))
+        .collect()
 }
 
-fn find_first_common_char(strings: &[&str]) -> Option<char> {
-    strings.first().and_then(|first| {
-        first
+fn parse_rucksack(line_number: usize, line: &str) -> Result<Rucksack> {
+    let items = line.chars().count();
+    if !items.is_multiple_of(2) {
+        return Err(eyre!(
+            "line {line_number}: {items} items can't be split into two equal compartments"
+        ));
+    }
+    let middle = line
+        .char_indices()
+        .nth(items / 2)
+        .map_or(line.len(), |(i, _)| i);
+    let (first, second) = line.split_at(middle);
+    let parse = |compartment: &str, offset: usize| {
+        compartment
             .chars()
-            .find(|fchar| strings.iter().all(|s| s.find(*fchar).is_some()))
+            .enumerate()
+            .try_fold(ItemSet::EMPTY, |set, (column, c)| {
+                set.with(c).ok_or_else(|| {
+                    eyre!(
+                        "line {line_number}, column {}: {c:?} is not an item",
+                        offset + column + 1
+                    )
+                })
+            })
+    };
+    Ok(Rucksack {
+        line: line_number,
+        first: parse(first, 0)?,
+        second: parse(second, items / 2)?,
     })
 }
 
-fn find_common_for_2(first: &str, second: &str) -> Option<char> {
-    find_first_common_char(&[first, second])
+/// `a`-`z` have priorities 1 to 26, `A`-`Z` 27 to 52
+pub fn char_priority(c: char) -> Option<u32> {
+    match c {
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
 }
 
-fn find_common_for_3(first: &str, second: &str, third: &str) -> Option<char> {
-    find_first_common_char(&[first, second, third])
+fn priority_char(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + (priority - 1) as u8) as char,
+        _ => (b'A' + (priority - 27) as u8) as char,
+    }
 }
 
-fn char_priority(c: char) -> u32 {
-    match c {
-        'a'..='z' => c as u32 - 'a' as u32 + 1,
-        'A'..='Z' => c as u32 - 'A' as u32 + 27,
-        _ => 0,
+/// A set of items, as one bit per priority
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+
+    /// The set with `item` added, `None` if it isn't an item
+    pub fn with(self, item: char) -> Option<Self> {
+        Some(ItemSet(self.0 | 1 << (char_priority(item)? - 1)))
+    }
+
+    pub fn contains(self, item: char) -> bool {
+        char_priority(item).is_some_and(|p| self.0 & 1 << (p - 1) != 0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The item if the set has exactly one
+    pub fn single(self) -> Option<char> {
+        (self.len() == 1).then(|| priority_char(self.0.trailing_zeros() + 1))
+    }
+
+    /// Items by increasing priority
+    pub fn items(self) -> impl Iterator<Item = char> {
+        (1..=52)
+            .filter(move |p| self.0 & 1 << (p - 1) != 0)
+            .map(priority_char)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        ItemSet(self.0 & !other.0)
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl Sub for ItemSet {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl FromIterator<char> for ItemSet {
+    /// Anything that isn't an item is left out
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        iter.into_iter()
+            .fold(ItemSet::EMPTY, |set, c| set.with(c).unwrap_or(set))
+    }
+}
+
+impl Display for ItemSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{{}}}", self.items().collect::<String>())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        char_priority, find_common_for_2, sum_of_common_priorities, sum_of_elf_group_priorities,
+    use super::{
+        badge, char_priority, groups, parse_rucksacks, sum_of_common_priorities,
+        sum_of_elf_group_priorities, ItemSet,
     };
 
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
     #[test]
-    fn find_common_test() {
-        assert_eq!(find_common_for_2("abcdef", "pqrsxyza"), Some('a'));
-        assert_eq!(find_common_for_2("vJrwpWtwJgWr", "hcsFMMfFFhFp"), Some('p'));
+    fn item_set_test() {
+        let a = "abcdef".chars().collect::<ItemSet>();
+        let b = "pqrsxyza".chars().collect::<ItemSet>();
+        assert_eq!(Some('a'), (a & b).single());
+        assert_eq!("{abcdefpqrsxyz}", (a | b).to_string());
+        assert_eq!("{bcdef}", (a - b).to_string());
+        assert_eq!(6, a.len());
+        assert!(a.contains('f') && !a.contains('F') && !a.contains('1'));
+        assert_eq!(None, a.single());
+        assert!(ItemSet::EMPTY.is_empty());
         assert_eq!(
-            find_common_for_2("jqHRNqRjqzjGDLGL", "rsFMfFZSrLrFZsSL"),
-            Some('L')
+            vec!['z', 'A', 'Z'],
+            "ZzA"
+                .chars()
+                .collect::<ItemSet>()
+                .items()
+                .collect::<Vec<_>>()
         );
     }
 
+    #[test]
+    fn find_common_test() {
+        let rucksacks = parse_rucksacks(EXAMPLE).unwrap();
+        assert_eq!(Some('p'), rucksacks[0].common().single());
+        assert_eq!(Some('L'), rucksacks[1].common().single());
+    }
+
     #[test]
     fn char_priority_test() {
-        assert_eq!(char_priority('a'), 1);
-        assert_eq!(char_priority('z'), 26);
-        assert_eq!(char_priority('A'), 27);
-        assert_eq!(char_priority('Z'), 52);
+        assert_eq!(char_priority('a'), Some(1));
+        assert_eq!(char_priority('z'), Some(26));
+        assert_eq!(char_priority('A'), Some(27));
+        assert_eq!(char_priority('Z'), Some(52));
+        assert_eq!(char_priority('!'), None);
     }
 
     #[test]
     fn example_part1() {
-        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw";
-
-        assert_eq!(sum_of_common_priorities(input), 157);
+        let rucksacks = parse_rucksacks(EXAMPLE).unwrap();
+        assert_eq!(sum_of_common_priorities(&rucksacks).unwrap(), 157);
     }
 
     #[test]
     fn example_part2() {
-        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw";
-        assert_eq!(sum_of_elf_group_priorities(input), 70);
+        let rucksacks = parse_rucksacks(EXAMPLE).unwrap();
+        assert_eq!(sum_of_elf_group_priorities(&rucksacks, 3).unwrap(), 70);
+    }
+
+    #[test]
+    fn group_size_test() {
+        let rucksacks = parse_rucksacks(EXAMPLE).unwrap();
+        assert_eq!(3, groups(&rucksacks, 2).unwrap().count());
+        assert_eq!('r', badge(&rucksacks[..3]).unwrap());
+        assert!(badge(&rucksacks).is_err());
+
+        let pairs = parse_rucksacks("abcX\nXdef\nghiZ\nZjkl").unwrap();
+        assert_eq!(50 + 52, sum_of_elf_group_priorities(&pairs, 2).unwrap());
+    }
+
+    #[test]
+    fn invalid_input_test() {
+        let error = parse_rucksacks("abcd\nabc\n").unwrap_err();
+        assert_eq!(
+            "line 2: 3 items can't be split into two equal compartments",
+            error.to_string()
+        );
+        let error = parse_rucksacks("ab1b").unwrap_err();
+        assert_eq!("line 1, column 3: '1' is not an item", error.to_string());
+        let error = parse_rucksacks("aébc").unwrap_err();
+        assert_eq!("line 1, column 2: 'é' is not an item", error.to_string());
+
+        let rucksacks = parse_rucksacks(EXAMPLE).unwrap();
+        let error = groups(&rucksacks[..4], 3).unwrap_err();
+        assert_eq!(
+            "line 4: incomplete group, 1 rucksacks left over after groups of 3",
+            error.to_string()
+        );
+        let error = sum_of_common_priorities(&parse_rucksacks("abab").unwrap()).unwrap_err();
+        assert_eq!(
+            "line 1: compartments share {ab}, expected exactly one item",
+            error.to_string()
+        );
     }
 }
@@ -0,0 +1,288 @@
+use std::fmt::Display;
+
+use color_eyre::eyre::{eyre, Result};
+
+use super::{ItemSet, Rucksack};
+
+/// Rucksacks (by index) carried by a group of elves, and the one item they all share
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub members: Vec<usize>,
+    pub badge: char,
+}
+
+/// Groups covering every rucksack exactly once, ordered by their first member
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partition {
+    pub groups: Vec<Group>,
+}
+
+/// Why the rucksacks can't be split into groups sharing a single badge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// The number of rucksacks isn't a multiple of the group size
+    IncompleteGroup { rucksacks: usize, size: usize },
+    /// No group containing the rucksack on this line shares exactly one item
+    NoGroup { line: usize },
+    /// Every way of grouping the others leaves the rucksack on this line without a group. This is
+    /// where the search got furthest, with `placed` groups formed
+    Stuck { line: usize, placed: usize },
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conflict::IncompleteGroup { rucksacks, size } => write!(
+                f,
+                "{rucksacks} rucksacks can't be split into groups of {size}"
+            ),
+            Conflict::NoGroup { line } => write!(
+                f,
+                "line {line}: the rucksack doesn't share exactly one item with any possible group"
+            ),
+            Conflict::Stuck { line, placed } => write!(
+                f,
+                "line {line}: every group left for the rucksack conflicts with the groups chosen \
+                 for the others (got furthest with {placed} groups formed)"
+            ),
+        }
+    }
+}
+
+/// Result of searching for the ways to group the rucksacks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Assignment {
+    /// `exhaustive` is false if the search stopped at the limit with more partitions left
+    Found {
+        partitions: Vec<Partition>,
+        exhaustive: bool,
+    },
+    Impossible(Conflict),
+}
+
+/// Splits the rucksacks (in any order) into groups of `size` where every group shares exactly one
+/// item, listing up to `limit` of the possible partitions. Listing none of them is an error, as
+/// nothing would tell whether there are any
+pub fn assign_badges(rucksacks: &[Rucksack], size: usize, limit: usize) -> Result<Assignment> {
+    if limit == 0 {
+        return Err(eyre!("At least one partition has to be listed"));
+    }
+    if size == 0 || !rucksacks.len().is_multiple_of(size) {
+        return Ok(Assignment::Impossible(Conflict::IncompleteGroup {
+            rucksacks: rucksacks.len(),
+            size,
+        }));
+    }
+
+    let items = rucksacks.iter().map(Rucksack::items).collect::<Vec<_>>();
+    // the groups each rucksack can form with rucksacks after it, as the search always places the
+    // first rucksack without a group next
+    let candidates = (0..items.len())
+        .map(|first| candidate_groups(&items, first, size))
+        .collect::<Vec<_>>();
+    for (i, rucksack) in rucksacks.iter().enumerate() {
+        let in_any_group = candidates
+            .iter()
+            .flatten()
+            .any(|group| group.members.contains(&i));
+        if !in_any_group {
+            return Ok(Assignment::Impossible(Conflict::NoGroup {
+                line: rucksack.line,
+            }));
+        }
+    }
+
+    let mut search = Search {
+        candidates: &candidates,
+        assigned: vec![false; items.len()],
+        groups: Vec::new(),
+        partitions: Vec::new(),
+        limit,
+        stuck: (0, 0),
+    };
+    let exhaustive = search.place();
+
+    if search.partitions.is_empty() && exhaustive {
+        let (placed, stuck) = search.stuck;
+        return Ok(Assignment::Impossible(Conflict::Stuck {
+            line: rucksacks[stuck].line,
+            placed,
+        }));
+    }
+    Ok(Assignment::Found {
+        partitions: search.partitions,
+        exhaustive,
+    })
+}
+
+/// Groups of `size` with `first` as the lowest index that share exactly one item
+fn candidate_groups(items: &[ItemSet], first: usize, size: usize) -> Vec<Group> {
+    fn extend(
+        items: &[ItemSet],
+        members: &mut Vec<usize>,
+        common: ItemSet,
+        size: usize,
+        groups: &mut Vec<Group>,
+    ) {
+        if members.len() == size {
+            if let Some(badge) = common.single() {
+                groups.push(Group {
+                    members: members.clone(),
+                    badge,
+                });
+            }
+            return;
+        }
+        let last = *members
+            .last()
+            .expect("groups start with their first member");
+        for next in last + 1..items.len() {
+            let common = common & items[next];
+            // sharing items only ever shrinks as the group grows
+            if !common.is_empty() {
+                members.push(next);
+                extend(items, members, common, size, groups);
+                members.pop();
+            }
+        }
+    }
+
+    let mut groups = Vec::new();
+    extend(items, &mut vec![first], items[first], size, &mut groups);
+    groups
+}
+
+struct Search<'a> {
+    candidates: &'a [Vec<Group>],
+    assigned: Vec<bool>,
+    groups: Vec<Group>,
+    partitions: Vec<Partition>,
+    limit: usize,
+    /// Most groups placed before getting stuck, and the rucksack that couldn't be placed then
+    stuck: (usize, usize),
+}
+
+impl Search<'_> {
+    /// Returns false once the limit is reached
+    fn place(&mut self) -> bool {
+        let Some(first) = self.assigned.iter().position(|a| !a) else {
+            if self.partitions.len() == self.limit {
+                return false;
+            }
+            self.partitions.push(Partition {
+                groups: self.groups.clone(),
+            });
+            return true;
+        };
+
+        let mut placed_any = false;
+        for group in &self.candidates[first] {
+            if group.members.iter().any(|m| self.assigned[*m]) {
+                continue;
+            }
+            placed_any = true;
+            for m in &group.members {
+                self.assigned[*m] = true;
+            }
+            self.groups.push(group.clone());
+            let go_on = self.place();
+            self.groups.pop();
+            for m in &group.members {
+                self.assigned[*m] = false;
+            }
+            if !go_on {
+                return false;
+            }
+        }
+        if !placed_any && self.groups.len() >= self.stuck.0 {
+            self.stuck = (self.groups.len(), first);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assign_badges, Assignment, Conflict, Group};
+    use crate::day_3::parse_rucksacks;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn discover_groups_test() {
+        // shuffle the example so the groups aren't consecutive
+        let lines = EXAMPLE.lines().collect::<Vec<_>>();
+        let shuffled = [3, 0, 5, 1, 4, 2].map(|i| lines[i]).join("\n");
+        let rucksacks = parse_rucksacks(&shuffled).unwrap();
+
+        let Assignment::Found {
+            partitions,
+            exhaustive,
+        } = assign_badges(&rucksacks, 3, 100).unwrap()
+        else {
+            panic!("the example can be grouped");
+        };
+        assert!(exhaustive);
+        assert!(partitions.iter().all(|p| p.groups.len() == 2));
+        // the puzzle's grouping is one of them
+        assert!(partitions.iter().any(|p| p.groups
+            == vec![
+                Group {
+                    members: vec![0, 2, 4],
+                    badge: 'Z'
+                },
+                Group {
+                    members: vec![1, 3, 5],
+                    badge: 'r'
+                }
+            ]));
+
+        let Assignment::Found {
+            partitions,
+            exhaustive,
+        } = assign_badges(&rucksacks, 3, 1).unwrap()
+        else {
+            panic!("the example can be grouped");
+        };
+        assert_eq!(1, partitions.len());
+        assert!(!exhaustive);
+    }
+
+    #[test]
+    fn conflict_test() {
+        let rucksacks = parse_rucksacks("abcd\naxef\nghij\naykl").unwrap();
+        assert_eq!(
+            Assignment::Impossible(Conflict::IncompleteGroup {
+                rucksacks: 4,
+                size: 3
+            }),
+            assign_badges(&rucksacks, 3, 10).unwrap()
+        );
+        // `ghij` shares nothing with the others
+        assert_eq!(
+            Assignment::Impossible(Conflict::NoGroup { line: 3 }),
+            assign_badges(&rucksacks, 2, 10).unwrap()
+        );
+
+        // matching the letters, or one pair sharing x, one sharing y and one matching letter
+        let rucksacks = parse_rucksacks("ax\nbx\ncx\nay\nby\ncy").unwrap();
+        let Assignment::Found { partitions, .. } = assign_badges(&rucksacks, 2, 10).unwrap() else {
+            panic!("pairs sharing x or y exist");
+        };
+        assert_eq!(1 + 3, partitions.len());
+
+        // the first rucksack can pair with any other, but they can't pair with each other
+        let rucksacks = parse_rucksacks("abca\naa\nbb\ncc").unwrap();
+        assert_eq!(
+            Assignment::Impossible(Conflict::Stuck { line: 2, placed: 1 }),
+            assign_badges(&rucksacks, 2, 10).unwrap()
+        );
+        // there would be nothing to show for finding groups
+        assert!(assign_badges(&rucksacks, 2, 0).is_err());
+    }
+}
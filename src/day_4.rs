@@ -1,38 +1,60 @@
-use std::str::FromStr;
-
-fn main() -> color_eyre::eyre::Result<()> {
-    color_eyre::install()?;
-
-    println!("Day 4");
-    let input = include_str!("input/day_4.txt");
-    println!(
-        "Part 1: {}",
-        fully_overlapping_pair_count(input, Overlap::Full)?
-    );
-    println!(
-        "Part 2: {}",
-        fully_overlapping_pair_count(input, Overlap::Partial)?
-    );
-
-    Ok(())
+use color_eyre::eyre::{eyre, Result};
+
+use crate::{interval::Interval, solution::Solution};
+
+pub mod rota;
+
+pub struct Day4;
+
+/// Sections assigned to one elf
+pub type Sections = Interval<u64>;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<(Sections, Sections)>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_pairs(input)
+    }
+
+    fn part_one(pairs: &Self::Input) -> Result<Self::PartOne> {
+        Ok(fully_overlapping_pair_count(pairs, Overlap::Full))
+    }
+
+    fn part_two(pairs: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(fully_overlapping_pair_count(pairs, Overlap::Partial))
+    }
 }
 
-fn fully_overlapping_pair_count(
-    input: &str,
-    overlap_type: Overlap,
-) -> color_eyre::eyre::Result<u64> {
+pub fn parse_pairs(input: &str) -> Result<Vec<(Sections, Sections)>> {
     input
         .lines()
         .flat_map(|line| line.split_once(','))
-        .map(|(first, second)| -> Result<u64, color_eyre::eyre::Report> {
-            let first_pair = Pair::from_str(first)?;
-            let second_pair = Pair::from_str(second)?;
+        .map(|(first, second)| Ok((parse_sections(first)?, parse_sections(second)?)))
+        .collect()
+}
+
+/// Parses a range of sections like `2-4`
+pub fn parse_sections(s: &str) -> Result<Sections> {
+    let (start, end) = s
+        .split_once('-')
+        .ok_or_else(|| eyre!("Cannot parse pair from string"))?;
+    Interval::new(start.parse()?, end.parse()?)
+        .ok_or_else(|| eyre!("Sections {s:?} end before they start"))
+}
 
+fn fully_overlapping_pair_count(pairs: &[(Sections, Sections)], overlap_type: Overlap) -> u64 {
+    pairs
+        .iter()
+        .map(|(first, second)| -> u64 {
             match overlap_type {
-                Overlap::Full => Ok((first_pair.contains(&second_pair)
-                    || second_pair.contains(&first_pair))
-                .into()),
-                Overlap::Partial => Ok(first_pair.overlaps(&second_pair).into()),
+                Overlap::Full => {
+                    (first.contains_interval(second) || second.contains_interval(first)).into()
+                }
+                Overlap::Partial => first.overlaps(second).into(),
             }
         })
         .sum()
@@ -43,38 +65,9 @@ enum Overlap {
     Full,
 }
 
-struct Pair(u64, u64);
-
-impl FromStr for Pair {
-    type Err = color_eyre::eyre::Report;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((start, end)) = s.split_once('-') {
-            let start = start.parse::<u64>()?;
-            let end = end.parse::<u64>()?;
-
-            Ok(Pair(start, end))
-        } else {
-            Err(color_eyre::eyre::eyre!("Cannot parse pair from string"))
-        }
-    }
-}
-
-impl Pair {
-    fn contains(&self, other: &Pair) -> bool {
-        self.0 <= other.0 && self.1 >= other.1
-    }
-
-    fn overlaps(&self, other: &Pair) -> bool {
-        // other.0 >= self.0 && other.0 <= self.1 || other.1 >= self.0 && other.0 <= self.1 || other.contains(self)
-        // clippy told this, above is the naive expression
-        (other.1 >= self.0 || other.0 >= self.0) && other.0 <= self.1 || other.contains(self)
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::{fully_overlapping_pair_count, Overlap};
+    use super::{fully_overlapping_pair_count, parse_pairs, parse_sections, Overlap};
 
     #[test]
     fn example_part1() {
@@ -86,7 +79,10 @@ mod tests {
 2-6,4-8";
 
         assert_eq!(
-            fully_overlapping_pair_count(input, Overlap::Full).expect("example should work"),
+            fully_overlapping_pair_count(
+                &parse_pairs(input).expect("example should work"),
+                Overlap::Full
+            ),
             2
         );
     }
@@ -100,8 +96,21 @@ mod tests {
 2-6,4-8";
 
         assert_eq!(
-            fully_overlapping_pair_count(input, Overlap::Partial).expect("example should work"),
+            fully_overlapping_pair_count(
+                &parse_pairs(input).expect("example should work"),
+                Overlap::Partial
+            ),
             4
         );
     }
+
+    #[test]
+    fn parse_sections_test() {
+        assert_eq!(Some(3), parse_sections("2-4").unwrap().len());
+        assert!(parse_sections("24").is_err());
+        assert_eq!(
+            "Sections \"4-2\" end before they start",
+            parse_sections("4-2").unwrap_err().to_string()
+        );
+    }
 }
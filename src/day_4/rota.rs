@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use crate::interval::{Interval, IntervalSet, IntervalTree};
+
+use super::Sections;
+
+/// Every elf's sections from all the pairs, for queries across pairs. Elves are numbered from 1
+/// in input order, two per pair
+pub struct Assignments {
+    elves: Vec<Sections>,
+    tree: IntervalTree<u64, usize>,
+}
+
+impl Assignments {
+    pub fn new(pairs: &[(Sections, Sections)]) -> Self {
+        let elves = pairs
+            .iter()
+            .flat_map(|(first, second)| [*first, *second])
+            .collect::<Vec<_>>();
+        let tree = elves
+            .iter()
+            .enumerate()
+            .map(|(i, sections)| (*sections, i + 1))
+            .collect();
+        Assignments { elves, tree }
+    }
+
+    /// Sections of the elf with that number
+    pub fn elf(&self, elf: usize) -> Option<Sections> {
+        elf.checked_sub(1).and_then(|i| self.elves.get(i)).copied()
+    }
+
+    /// Elves that have to clean `section`, by number
+    pub fn covering(&self, section: u64) -> Vec<usize> {
+        let mut elves = self
+            .tree
+            .containing(section)
+            .into_iter()
+            .map(|(_, elf)| *elf)
+            .collect::<Vec<_>>();
+        elves.sort_unstable();
+        elves
+    }
+
+    /// Every two elves, in or across pairs, that share at least one section
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = self
+            .elves
+            .iter()
+            .enumerate()
+            .flat_map(|(i, sections)| {
+                self.tree
+                    .overlapping(*sections)
+                    .into_iter()
+                    .filter(move |(_, other)| **other > i + 1)
+                    .map(move |(_, other)| (i + 1, *other))
+            })
+            .collect::<Vec<_>>();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// From the first assigned section to the last, the elves on each run of sections with the
+    /// same elves assigned
+    pub fn rota(&self) -> Rota {
+        // the sections where the set of elves can change, wide enough for the one after u64::MAX
+        let mut boundaries = self
+            .elves
+            .iter()
+            .flat_map(|sections| [sections.start() as u128, sections.end() as u128 + 1])
+            .collect::<Vec<_>>();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let shifts = boundaries
+            .windows(2)
+            .map(|w| Shift {
+                sections: Interval::new(w[0] as u64, (w[1] - 1) as u64)
+                    .expect("boundaries are sorted and unique"),
+                elves: self.covering(w[0] as u64),
+            })
+            .collect();
+        Rota(shifts)
+    }
+
+    /// Sections between the first and the last assigned that no elf cleans
+    pub fn uncovered(&self) -> IntervalSet<u64> {
+        self.sections_where(|elves| elves == 0)
+    }
+
+    /// Sections cleaned by more than `limit` elves
+    pub fn crowded(&self, limit: usize) -> IntervalSet<u64> {
+        self.sections_where(|elves| elves > limit)
+    }
+
+    /// The most elves cleaning the same section, and the sections where that happens
+    pub fn max_overlap(&self) -> (usize, IntervalSet<u64>) {
+        let most = self
+            .rota()
+            .0
+            .iter()
+            .map(|shift| shift.elves.len())
+            .max()
+            .unwrap_or(0);
+        (most, self.sections_where(|elves| elves == most))
+    }
+
+    fn sections_where(&self, predicate: impl Fn(usize) -> bool) -> IntervalSet<u64> {
+        self.rota()
+            .0
+            .into_iter()
+            .filter(|shift| predicate(shift.elves.len()))
+            .map(|shift| shift.sections)
+            .collect()
+    }
+}
+
+/// Sections that the same elves clean
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shift {
+    pub sections: Sections,
+    pub elves: Vec<usize>,
+}
+
+/// Who cleans what, section by section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rota(pub Vec<Shift>);
+
+impl Display for Rota {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|shift| shift.sections.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max("Sections".len());
+        writeln!(f, "{:<width$} | Count | Elves", "Sections")?;
+        write!(f, "{:-<width$}-+-------+------", "")?;
+        for shift in &self.0 {
+            let elves = if shift.elves.is_empty() {
+                "nobody".to_string()
+            } else {
+                shift
+                    .elves
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            write!(
+                f,
+                "\n{:<width$} | {:>5} | {elves}",
+                shift.sections.to_string(),
+                shift.elves.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Assignments;
+    use crate::day_4::parse_pairs;
+
+    const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    #[test]
+    fn queries_test() {
+        let assignments = Assignments::new(&parse_pairs(EXAMPLE).unwrap());
+        assert_eq!(
+            Some("6-8".to_string()),
+            assignments.elf(2).map(|s| s.to_string())
+        );
+        assert_eq!(None, assignments.elf(0));
+        assert_eq!(vec![2, 5, 7, 8, 9, 10, 11, 12], assignments.covering(6));
+        assert!(assignments.covering(1).is_empty());
+        // 2-4 overlaps elves from other pairs, but not its own partner
+        assert_eq!(
+            vec![(1, 3), (1, 4), (1, 7), (1, 8), (1, 10), (1, 11), (1, 12)],
+            assignments
+                .overlapping_pairs()
+                .into_iter()
+                .filter(|(a, _)| *a == 1)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!((8, "6-6".to_string()), {
+            let (most, sections) = assignments.max_overlap();
+            (most, sections.to_string())
+        });
+    }
+
+    #[test]
+    fn coverage_test() {
+        let assignments = Assignments::new(&parse_pairs("1-3,7-9\n2-4,9-9").unwrap());
+        assert_eq!("5-6", assignments.uncovered().to_string());
+        assert_eq!("2-3, 9-9", assignments.crowded(1).to_string());
+        assert_eq!((2, assignments.crowded(1)), assignments.max_overlap());
+        assert_eq!(vec![(1, 3), (2, 4)], assignments.overlapping_pairs());
+        assert_eq!(
+            "Sections | Count | Elves
+---------+-------+------
+1-1      |     1 | 1
+2-3      |     2 | 1, 3
+4-4      |     1 | 3
+5-6      |     0 | nobody
+7-8      |     1 | 2
+9-9      |     2 | 2, 4",
+            assignments.rota().to_string()
+        );
+    }
+
+    #[test]
+    fn last_section_test() {
+        let assignments = Assignments::new(&parse_pairs("5-18446744073709551615,2-6").unwrap());
+        assert_eq!(
+            vec![
+                ("2-4".to_string(), vec![2]),
+                ("5-6".to_string(), vec![1, 2]),
+                (format!("7-{}", u64::MAX), vec![1])
+            ],
+            assignments
+                .rota()
+                .0
+                .into_iter()
+                .map(|shift| (shift.sections.to_string(), shift.elves))
+                .collect::<Vec<_>>()
+        );
+        assert!(assignments.uncovered().is_empty());
+    }
+}
@@ -1,101 +1,103 @@
+use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
-use std::collections::HashMap;
-
-fn main() -> color_eyre::eyre::Result<()> {
-    color_eyre::install()?;
-    println!("Day 5");
-    let input = include_str!("input/day_5.txt");
-    println!(
-        "Part 1: {}",
-        top_crates_of_stacks(input, CrateMoverModel::CrateMover9000)?
-    );
-    println!(
-        "Part 2: {}",
-        top_crates_of_stacks(input, CrateMoverModel::CrateMover9001)?
-    );
-    Ok(())
-}
+use nom::{
+    bytes::complete::tag,
+    combinator::map,
+    error::context,
+    sequence::{preceded, tuple},
+};
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    parse::{self, ParseResult},
+    solution::Solution,
+};
+
+use self::{
+    crane::{CrateMover, CrateMover9000, CrateMover9001},
+    validate::Problem,
+};
+
+pub mod crane;
+pub mod planner;
+pub mod replay;
+pub mod validate;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Procedure;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_procedure(input)
+    }
 
-fn top_crates_of_stacks(input: &str, model: CrateMoverModel) -> color_eyre::eyre::Result<String> {
-    let mut stacks = parse_stacks(input)?;
-    let instructions = parse_instructions(input)?;
-    for instruction in instructions {
-        // dbg!(&instruction);
-        // dbg!(&stacks);
-        match model {
-            CrateMoverModel::CrateMover9000 => {
-                for _ in 0..instruction.quantity {
-                    let to_move = stacks
-                        .0
-                        .get_mut(&instruction.start)
-                        .ok_or(color_eyre::eyre::eyre!("Start not found"))?;
-                    let c = to_move.pop().ok_or(color_eyre::eyre::eyre!(
-                        "Start element does not have enough elements"
-                    ))?;
-                    stacks
-                        .0
-                        .entry(instruction.end)
-                        .or_insert(Vec::new())
-                        .push(c);
-                }
-            }
-            CrateMoverModel::CrateMover9001 => {
-                let start_stack = stacks
-                    .0
-                    .get_mut(&instruction.start)
-                    .ok_or(color_eyre::eyre::eyre!("Start not found"))?;
-                let mut to_move = Vec::with_capacity(instruction.quantity);
-                for i in &start_stack[(start_stack.len() - instruction.quantity)..] {
-                    to_move.push(i.to_owned());
-                }
-                start_stack.truncate(start_stack.len() - instruction.quantity);
+    fn part_one(procedure: &Self::Input) -> Result<Self::PartOne> {
+        top_crates_of_stacks(procedure, &CrateMover9000)
+    }
 
-                let end_stack = stacks.0.entry(instruction.end).or_insert(Vec::new());
-                (0..instruction.quantity).for_each(|i| end_stack.push(to_move[i]));
-            }
-        }
+    fn part_two(procedure: &Self::Input) -> Result<Self::PartTwo> {
+        top_crates_of_stacks(procedure, &CrateMover9001)
     }
+}
+
+/// The crate on top of each stack after `crane` carried out the procedure
+pub fn top_crates_of_stacks(procedure: &Procedure, crane: &dyn CrateMover) -> Result<String> {
+    Ok(procedure.validate(crane)?.top_crates())
+}
 
-    // dbg!(&stacks);
-    Ok(stacks
-        .0
-        .iter()
-        .sorted()
-        .filter_map(|(_, stack)| stack.last().cloned())
-        .join(""))
+pub fn parse_procedure(input: &str) -> Result<Procedure> {
+    let (instruction_lines, instructions) = parse_instructions(input)?.into_iter().unzip();
+    Ok(Procedure {
+        stacks: parse_stacks(input)?,
+        instructions,
+        instruction_lines,
+    })
 }
 
-fn parse_instructions(input: &str) -> color_eyre::eyre::Result<Vec<MoveInstruction>> {
+/// Instructions with the line they're on
+fn parse_instructions(input: &str) -> Result<Vec<(usize, MoveInstruction)>> {
     input
         .lines()
-        .filter(|l| l.starts_with("move"))
-        .map(|l| {
-            let parsed_numbers = l
-                .split_whitespace()
-                .filter_map(|s| s.parse::<usize>().ok())
-                .collect::<Vec<_>>();
-
-            Ok(MoveInstruction {
-                quantity: *parsed_numbers.first().ok_or(color_eyre::eyre::eyre!(
-                    "Move instruction is malformed (1st number not found)"
-                ))?,
-                start: *parsed_numbers.get(1).ok_or(color_eyre::eyre::eyre!(
-                    "Move instruction is malformed (2nd number not found)"
-                ))?,
-                end: *parsed_numbers.get(2).ok_or(color_eyre::eyre::eyre!(
-                    "Move instruction is malformed (3rd number not found)"
-                ))?,
-            })
-        })
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("move"))
+        .map(|(i, l)| Ok((i + 1, parse::finish(input, parse_instruction(l))?)))
         .collect::<Result<Vec<_>, _>>()
 }
 
-fn parse_stacks(input: &str) -> color_eyre::eyre::Result<Stacks> {
+// move 1 from 2 to 1
+fn parse_instruction(line: &str) -> ParseResult<'_, MoveInstruction> {
+    context(
+        "move instruction",
+        map(
+            tuple((
+                preceded(tag("move "), context("quantity", usize)),
+                preceded(tag(" from "), context("start stack", usize)),
+                preceded(tag(" to "), context("end stack", usize)),
+            )),
+            |(quantity, start, end)| MoveInstruction {
+                quantity,
+                start,
+                end,
+            },
+        ),
+    )(line)
+}
+
+fn usize(s: &str) -> ParseResult<'_, usize> {
+    map(nom::character::complete::u64, |n| n as usize)(s)
+}
+
+fn parse_stacks(input: &str) -> Result<Stacks> {
     let mut parsed_grid = input
         .lines()
         .skip_while(|l| l.is_empty())
         .take_while(|l| l.trim().starts_with('['))
-        .map(|l| -> color_eyre::eyre::Result<_> {
+        .map(|l| -> Result<_> {
             let mut current_pos = 0;
             let mut h_crates: Vec<Option<Crate>> = Vec::new();
             while current_pos <= l.len() {
@@ -109,7 +111,7 @@ fn parse_stacks(input: &str) -> color_eyre::eyre::Result<Stacks> {
                 let found_crate = current_crate
                     .chars()
                     .nth(1)
-                    .ok_or(color_eyre::eyre::eyre!("Crate is not of format [{{char}}]"))?;
+                    .ok_or_else(|| eyre!("Crate is not of format [{{char}}]"))?;
                 h_crates.push(Some(found_crate));
                 current_pos += 4;
             }
@@ -119,11 +121,21 @@ fn parse_stacks(input: &str) -> color_eyre::eyre::Result<Stacks> {
         .collect::<Result<Vec<_>, _>>()?;
     parsed_grid.reverse();
 
-    let mut stacks = Stacks(HashMap::new());
+    // columns without any crates are still stacks crates can be moved to
+    let mut stacks = Stacks(
+        input
+            .lines()
+            .find(|l| !l.is_empty() && !l.trim().starts_with('['))
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter_map(|number| number.parse().ok())
+            .map(|number| (number, Vec::new()))
+            .collect(),
+    );
     for row in parsed_grid {
         row.iter().enumerate().for_each(|(i, possile_crate)| {
             if let Some(c) = possile_crate {
-                stacks.0.entry(i + 1).or_insert(Vec::new()).push(*c);
+                stacks.0.entry(i + 1).or_default().push(*c);
             }
         })
     }
@@ -131,34 +143,124 @@ fn parse_stacks(input: &str) -> color_eyre::eyre::Result<Stacks> {
     Ok(stacks)
 }
 
-type Crate = char;
+pub type Crate = char;
+
+/// Crates of each stack by stack number, bottom crate first
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Stacks(pub HashMap<usize, Vec<Crate>>);
+
+impl Stacks {
+    /// Carries out a single move with `crane`, leaving the stacks as they were if it can't
+    pub fn apply(
+        &mut self,
+        instruction: &MoveInstruction,
+        crane: &dyn CrateMover,
+    ) -> Result<(), Problem> {
+        if instruction.end == 0 {
+            return Err(Problem::NoStackZero);
+        }
+        if !self.0.contains_key(&instruction.end) {
+            return Err(Problem::MissingStack(instruction.end));
+        }
+        let start_stack = self
+            .0
+            .get_mut(&instruction.start)
+            .ok_or(Problem::MissingStack(instruction.start))?;
+        let remaining = start_stack.len().checked_sub(instruction.quantity).ok_or(
+            Problem::NotEnoughCrates {
+                stack: instruction.start,
+                crates: start_stack.len(),
+            },
+        )?;
+        let lifted = start_stack.split_off(remaining);
+        if instruction.start == instruction.end {
+            crane.put_back(lifted, start_stack);
+            return Ok(());
+        }
+        let end_stack = self
+            .0
+            .get_mut(&instruction.end)
+            .expect("the destination was checked");
+        crane.place(lifted, end_stack);
+        Ok(())
+    }
 
-#[derive(PartialEq, Eq, Debug)]
-struct Stacks(HashMap<usize, Vec<Crate>>);
+    /// The crate on top of each stack, in stack order, skipping empty stacks
+    pub fn top_crates(&self) -> String {
+        self.0
+            .iter()
+            .sorted()
+            .filter_map(|(_, stack)| stack.last().cloned())
+            .join("")
+    }
+}
 
-#[derive(PartialEq, Eq, Debug)]
-struct MoveInstruction {
-    quantity: usize,
-    start: usize,
-    end: usize,
+impl Display for Stacks {
+    /// Draws the stacks like the puzzle input, with a column for every stack up to the highest
+    /// numbered one
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let columns = self.0.keys().max().copied().unwrap_or(0);
+        let height = self.0.values().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row = (1..=columns)
+                .map(
+                    |i| match self.0.get(&i).and_then(|stack| stack.get(level)) {
+                        Some(c) => format!("[{c}]"),
+                        None => "   ".to_string(),
+                    },
+                )
+                .join(" ");
+            writeln!(f, "{row}")?;
+        }
+        write!(f, "{}", (1..=columns).map(|i| format!(" {i} ")).join(" "))
+    }
 }
 
-#[derive(PartialEq, Eq, Debug)]
-struct Procedure {
-    stacks: Stacks,
-    instructions: Vec<MoveInstruction>,
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct MoveInstruction {
+    pub quantity: usize,
+    pub start: usize,
+    pub end: usize,
 }
 
-enum CrateMoverModel {
-    CrateMover9000,
-    CrateMover9001,
+impl Display for MoveInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity, self.start, self.end
+        )
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Procedure {
+    pub stacks: Stacks,
+    pub instructions: Vec<MoveInstruction>,
+    /// Line of the input each instruction is on, empty if it wasn't parsed
+    pub instruction_lines: Vec<usize>,
+}
+
+impl Display for Procedure {
+    /// Writes the procedure back out as puzzle input
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.stacks)?;
+        for instruction in &self.instructions {
+            write!(f, "\n{instruction}")?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use crate::{parse_instructions, parse_stacks, top_crates_of_stacks, MoveInstruction, Stacks};
+    use super::{
+        crane::{CrateMover9000, CrateMover9001},
+        parse_instructions, parse_procedure, parse_stacks, top_crates_of_stacks, MoveInstruction,
+        Stacks,
+    };
 
     #[test]
     fn parse_stacks_test() {
@@ -191,11 +293,23 @@ move 1 from 2 to 1";
 
         assert_eq!(
             parsed_data,
-            vec![MoveInstruction {
-                quantity: 1,
-                start: 2,
-                end: 1
-            }]
+            vec![(
+                6,
+                MoveInstruction {
+                    quantity: 1,
+                    start: 2,
+                    end: 1
+                }
+            )]
+        );
+
+        let error = parse_instructions("move 1 from 2 to 1\nmove 1 from x to 3").unwrap_err();
+        assert_eq!(
+            "line 2, column 13: expected Digit in start stack in move instruction
+  |
+2 | move 1 from x to 3
+  |             ^",
+            error.to_string()
         );
     }
 
@@ -212,7 +326,7 @@ move 2 from 2 to 1
 move 1 from 1 to 2";
 
         assert_eq!(
-            top_crates_of_stacks(input, crate::CrateMoverModel::CrateMover9000).unwrap(),
+            top_crates_of_stacks(&parse_procedure(input).unwrap(), &CrateMover9000).unwrap(),
             "CMZ"
         );
     }
@@ -230,7 +344,7 @@ move 2 from 2 to 1
 move 1 from 1 to 2";
 
         assert_eq!(
-            top_crates_of_stacks(input, crate::CrateMoverModel::CrateMover9001).unwrap(),
+            top_crates_of_stacks(&parse_procedure(input).unwrap(), &CrateMover9001).unwrap(),
             "MCD"
         );
     }
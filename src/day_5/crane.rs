@@ -0,0 +1,205 @@
+use color_eyre::eyre::{eyre, Result};
+
+use super::Crate;
+
+/// How a crane puts down the crates it lifted off a stack
+pub trait CrateMover {
+    /// Name of the model, which [`crate_mover`] accepts back
+    fn name(&self) -> String;
+
+    /// Puts the crates `lifted` from the top of a stack, bottom one first, onto `stack`
+    fn place(&self, lifted: Vec<Crate>, stack: &mut Vec<Crate>);
+
+    /// Puts the crates `lifted` from the top of `stack` back onto it. Moving them one at a time
+    /// or all at once leaves every crate where it was
+    fn put_back(&self, lifted: Vec<Crate>, stack: &mut Vec<Crate>) {
+        stack.extend(lifted);
+    }
+}
+
+/// Moves crates one at a time, reversing their order
+pub struct CrateMover9000;
+
+impl CrateMover for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn place(&self, lifted: Vec<Crate>, stack: &mut Vec<Crate>) {
+        stack.extend(lifted.into_iter().rev());
+    }
+}
+
+/// Moves all the crates at once, keeping their order
+pub struct CrateMover9001;
+
+impl CrateMover for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn place(&self, lifted: Vec<Crate>, stack: &mut Vec<Crate>) {
+        stack.extend(lifted);
+    }
+}
+
+/// Moves up to `capacity` crates at once, making several trips for bigger moves
+pub struct LimitedCrateMover {
+    pub capacity: usize,
+}
+
+impl CrateMover for LimitedCrateMover {
+    fn name(&self) -> String {
+        format!("CrateMover limited:{}", self.capacity)
+    }
+
+    fn place(&self, lifted: Vec<Crate>, stack: &mut Vec<Crate>) {
+        for trip in lifted.rchunks(self.capacity.max(1)) {
+            stack.extend_from_slice(trip);
+        }
+    }
+}
+
+/// Moves all the crates at once, but spins them so the top `turns` crates end up at the bottom
+pub struct RotatingCrateMover {
+    pub turns: usize,
+}
+
+impl CrateMover for RotatingCrateMover {
+    fn name(&self) -> String {
+        format!("CrateMover rotating:{}", self.turns)
+    }
+
+    fn place(&self, mut lifted: Vec<Crate>, stack: &mut Vec<Crate>) {
+        if !lifted.is_empty() {
+            let turns = self.turns % lifted.len();
+            lifted.rotate_right(turns);
+        }
+        stack.extend(lifted);
+    }
+
+    /// The crates still spin on the way back
+    fn put_back(&self, lifted: Vec<Crate>, stack: &mut Vec<Crate>) {
+        self.place(lifted, stack);
+    }
+}
+
+/// Names accepted by [`crate_mover`]
+pub const MODELS: &[&str] = &["9000", "9001", "limited:<CAPACITY>", "rotating:<TURNS>"];
+
+/// Picks a model by name, see [`MODELS`]. Case and spaces don't matter and the names can start
+/// with `CrateMover`, so the names the models print are accepted too
+pub fn crate_mover(name: &str) -> Result<Box<dyn CrateMover>> {
+    let lowered = name
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    let normalized = lowered.strip_prefix("cratemover").unwrap_or(&lowered);
+    let (model, argument) = match normalized.split_once(':') {
+        Some((model, argument)) => (model, Some(argument)),
+        None => (normalized, None),
+    };
+    let number = |what: &str| -> Result<usize> {
+        argument
+            .ok_or_else(|| eyre!("The {model} crane needs {what}, like {model}:2"))?
+            .parse()
+            .map_err(|e| eyre!("Invalid {what} for the {model} crane: {e}"))
+    };
+    let fixed = |mover: Box<dyn CrateMover>| match argument {
+        Some(_) => Err(eyre!("The {model} crane doesn't take a setting")),
+        None => Ok(mover),
+    };
+    match model {
+        "9000" => fixed(Box::new(CrateMover9000)),
+        "9001" => fixed(Box::new(CrateMover9001)),
+        "limited" => {
+            let capacity = number("a capacity")?;
+            if capacity == 0 {
+                return Err(eyre!("A crane needs to lift at least one crate"));
+            }
+            Ok(Box::new(LimitedCrateMover { capacity }))
+        }
+        "rotating" => Ok(Box::new(RotatingCrateMover {
+            turns: number("a number of turns")?,
+        })),
+        _ => Err(eyre!(
+            "Unknown crane {name:?}, expected one of {}",
+            MODELS.join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crate_mover;
+    use crate::day_5::{parse_stacks, MoveInstruction};
+
+    fn place(name: &str) -> String {
+        let mut stack = vec!['Z'];
+        crate_mover(name)
+            .unwrap()
+            .place("ABCDE".chars().collect(), &mut stack);
+        stack.into_iter().collect()
+    }
+
+    #[test]
+    fn models_test() {
+        assert_eq!("ZEDCBA", place("9000"));
+        assert_eq!("ZABCDE", place("CrateMover9001"));
+        assert_eq!("ZDEBCA", place("limited:2"));
+        assert_eq!(place("9000"), place("limited:1"));
+        assert_eq!(place("9001"), place("limited:5"));
+        assert_eq!("ZDEABC", place("rotating:2"));
+        assert_eq!(place("9001"), place("rotating:5"));
+    }
+
+    #[test]
+    fn names_test() {
+        assert!(crate_mover("9002").is_err());
+        assert!(crate_mover("limited").is_err());
+        assert!(crate_mover("limited:0").is_err());
+        assert!(crate_mover("rotating:x").is_err());
+        assert!(crate_mover("9000:3").is_err());
+        assert_eq!(
+            "CrateMover limited:3",
+            crate_mover("limited:3").unwrap().name()
+        );
+        // what the models print can be given back
+        for name in ["9000", "9001", "limited:3", "rotating:2"] {
+            let printed = crate_mover(name).unwrap().name();
+            assert_eq!(printed, crate_mover(&printed).unwrap().name());
+        }
+        assert_eq!(
+            "CrateMover 9000",
+            crate_mover("cratemover 9000").unwrap().name()
+        );
+        assert_eq!(
+            "CrateMover 9001",
+            crate_mover("CRATEMOVER9001").unwrap().name()
+        );
+    }
+
+    #[test]
+    fn same_stack_test() {
+        let stacks = parse_stacks("[A]\n[B]\n[C]\n[D]\n 1 ").unwrap();
+        let instruction = MoveInstruction {
+            quantity: 3,
+            start: 1,
+            end: 1,
+        };
+        let moved = |name| {
+            let mut moved = stacks.clone();
+            moved
+                .apply(&instruction, &*crate_mover(name).unwrap())
+                .unwrap();
+            moved.0[&1].iter().collect::<String>()
+        };
+        for name in ["9000", "9001", "limited:2", "rotating:3"] {
+            assert_eq!("DCBA", moved(name), "{name}");
+        }
+        // the top crate spins to the bottom of the three lifted
+        assert_eq!("DACB", moved("rotating:1"));
+        assert_eq!("DBAC", moved("rotating:2"));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{HashMap, VecDeque};
+
+use itertools::Itertools;
+
+use super::{crane::CrateMover, Crate, MoveInstruction, Procedure, Stacks};
+
+/// Result of searching for a procedure that leaves a message on top of the stacks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Planning {
+    /// A procedure with as few instructions as possible
+    Found(Procedure),
+    /// No sequence of moves leaves the message on top
+    Impossible,
+    /// The search looked at `states` arrangements of the crates without finding the message
+    GaveUp { states: usize },
+}
+
+/// Searches breadth first for the shortest procedure with which `crane` leaves `message` on top
+/// of `stacks`, looking at up to `max_states` arrangements of the crates. Moves only go between
+/// the stacks up to the highest numbered one
+pub fn plan(stacks: &Stacks, message: &str, crane: &dyn CrateMover, max_states: usize) -> Planning {
+    let columns = stacks.0.keys().max().copied().unwrap_or(0);
+    let start = (1..=columns)
+        .map(|i| stacks.0.get(&i).cloned().unwrap_or_default())
+        .collect::<Vec<_>>();
+    if !reachable(&start, message) {
+        return Planning::Impossible;
+    }
+
+    // every arrangement seen, with the one it was reached from and how
+    let mut seen = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(arrangement) = queue.pop_front() {
+        if top_crates(&arrangement) == message {
+            return Planning::Found(Procedure {
+                stacks: stacks.clone(),
+                instructions: path_to(&seen, arrangement),
+                instruction_lines: Vec::new(),
+            });
+        }
+        for (instruction, next) in moves(&arrangement, crane) {
+            if seen.len() == max_states {
+                return Planning::GaveUp { states: seen.len() };
+            }
+            if !seen.contains_key(&next) {
+                seen.insert(next.clone(), Some((arrangement.clone(), instruction)));
+                queue.push_back(next);
+            }
+        }
+    }
+    Planning::Impossible
+}
+
+type Arrangement = Vec<Vec<Crate>>;
+
+fn top_crates(arrangement: &Arrangement) -> String {
+    arrangement
+        .iter()
+        .filter_map(|stack| stack.last())
+        .collect()
+}
+
+/// Whether there are enough stacks and crates to spell out the message at all
+fn reachable(arrangement: &Arrangement, message: &str) -> bool {
+    let available = arrangement.iter().flatten().counts();
+    message.chars().count() <= arrangement.len()
+        && message
+            .chars()
+            .counts()
+            .iter()
+            .all(|(c, n)| available.get(c).is_some_and(|a| a >= n))
+}
+
+/// Every arrangement one instruction away, or the same arrangement again
+fn moves<'a>(
+    arrangement: &'a Arrangement,
+    crane: &'a dyn CrateMover,
+) -> impl Iterator<Item = (MoveInstruction, Arrangement)> + 'a {
+    let stacks = arrangement.len();
+    (0..stacks)
+        .cartesian_product(0..stacks)
+        .flat_map(move |(start, end)| {
+            (1..=arrangement[start].len()).map(move |quantity| {
+                let mut next = arrangement.clone();
+                let remaining = next[start].len() - quantity;
+                let lifted = next[start].split_off(remaining);
+                // only some cranes change a stack by putting crates back onto it
+                if start == end {
+                    crane.put_back(lifted, &mut next[end]);
+                } else {
+                    crane.place(lifted, &mut next[end]);
+                }
+                let instruction = MoveInstruction {
+                    quantity,
+                    start: start + 1,
+                    end: end + 1,
+                };
+                (instruction, next)
+            })
+        })
+}
+
+fn path_to(
+    seen: &HashMap<Arrangement, Option<(Arrangement, MoveInstruction)>>,
+    mut arrangement: Arrangement,
+) -> Vec<MoveInstruction> {
+    let mut instructions = Vec::new();
+    while let Some(Some((previous, instruction))) = seen.get(&arrangement) {
+        instructions.push(*instruction);
+        arrangement = previous.clone();
+    }
+    instructions.reverse();
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{plan, Planning};
+    use crate::day_5::{
+        crane::{CrateMover, CrateMover9000, CrateMover9001, RotatingCrateMover},
+        parse_stacks, top_crates_of_stacks,
+    };
+
+    const EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3 ";
+
+    #[test]
+    fn plan_test() {
+        let stacks = parse_stacks(EXAMPLE).unwrap();
+        let found = |message, crane| match plan(&stacks, message, crane, 100_000) {
+            Planning::Found(procedure) => procedure,
+            other => panic!("no plan for {message}: {other:?}"),
+        };
+
+        assert!(found("NDP", &CrateMover9000).instructions.is_empty());
+        let procedure = found("DCP", &CrateMover9000);
+        assert_eq!(
+            vec!["move 1 from 2 to 1"],
+            procedure
+                .instructions
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+        );
+
+        for message in ["CMZ", "MCD", "ZMP", "P"] {
+            for crane in [&CrateMover9000 as &dyn CrateMover, &CrateMover9001] {
+                let procedure = found(message, crane);
+                assert_eq!(message, top_crates_of_stacks(&procedure, crane).unwrap());
+            }
+        }
+        // the example's own procedure takes 4 moves, but 2 are enough. It comes out as puzzle input
+        assert!(found("CMZ", &CrateMover9000)
+            .to_string()
+            .ends_with("\n 1   2   3 \n\nmove 2 from 1 to 3\nmove 2 from 2 to 1"));
+    }
+
+    #[test]
+    fn same_stack_plan_test() {
+        // only putting crates back onto their own stack brings Z to the top
+        let stacks = parse_stacks("[Y]\n[Z]\n 1 ").unwrap();
+        let crane = RotatingCrateMover { turns: 1 };
+        match plan(&stacks, "Z", &crane, 1000) {
+            Planning::Found(procedure) => assert_eq!(
+                vec!["move 2 from 1 to 1"],
+                procedure
+                    .instructions
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+            ),
+            other => panic!("no plan: {other:?}"),
+        }
+        assert_eq!(
+            Planning::Impossible,
+            plan(&stacks, "Z", &CrateMover9001, 1000)
+        );
+    }
+
+    #[test]
+    fn impossible_test() {
+        let stacks = parse_stacks(EXAMPLE).unwrap();
+        assert_eq!(
+            Planning::Impossible,
+            plan(&stacks, "ABC", &CrateMover9000, 1000)
+        );
+        assert_eq!(
+            Planning::Impossible,
+            plan(&stacks, "ZZ", &CrateMover9000, 1000)
+        );
+        assert_eq!(
+            Planning::Impossible,
+            plan(&stacks, "NDPC", &CrateMover9000, 1000)
+        );
+        assert_eq!(
+            Planning::GaveUp { states: 10 },
+            plan(&stacks, "ZMN", &CrateMover9000, 10)
+        );
+    }
+}
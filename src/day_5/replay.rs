@@ -0,0 +1,170 @@
+use color_eyre::eyre::Result;
+
+use super::{crane::CrateMover, MoveInstruction, Procedure, Stacks};
+
+/// Steps through a procedure one instruction at a time, keeping every state reached so far so
+/// steps can be undone and redone
+pub struct Replay<'a> {
+    procedure: &'a Procedure,
+    crane: &'a dyn CrateMover,
+    /// The stacks after each number of instructions, as far as the replay got
+    states: Vec<Stacks>,
+    position: usize,
+}
+
+impl<'a> Replay<'a> {
+    pub fn new(procedure: &'a Procedure, crane: &'a dyn CrateMover) -> Self {
+        Replay {
+            procedure,
+            crane,
+            states: vec![procedure.stacks.clone()],
+            position: 0,
+        }
+    }
+
+    /// Number of instructions carried out to get to the current state
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position == self.procedure.instructions.len()
+    }
+
+    pub fn stacks(&self) -> &Stacks {
+        &self.states[self.position]
+    }
+
+    /// Every state recorded so far, starting with the initial stacks
+    pub fn states(&self) -> &[Stacks] {
+        &self.states
+    }
+
+    /// The instruction that led to the current state
+    pub fn previous_instruction(&self) -> Option<&'a MoveInstruction> {
+        self.position
+            .checked_sub(1)
+            .and_then(|i| self.procedure.instructions.get(i))
+    }
+
+    pub fn next_instruction(&self) -> Option<&'a MoveInstruction> {
+        self.procedure.instructions.get(self.position)
+    }
+
+    /// Carries out the next instruction, returning false if there are none left
+    pub fn step(&mut self) -> Result<bool> {
+        if self.redo() {
+            return Ok(true);
+        }
+        if self.is_finished() {
+            return Ok(false);
+        }
+        let mut stacks = self.stacks().clone();
+        self.procedure
+            .apply(&mut stacks, self.position, self.crane)?;
+        self.states.push(stacks);
+        self.position += 1;
+        Ok(true)
+    }
+
+    /// Goes back to the state before the last instruction, returning false at the start
+    pub fn undo(&mut self) -> bool {
+        let undone = self.position > 0;
+        self.position = self.position.saturating_sub(1);
+        undone
+    }
+
+    /// Goes forward to a state that was undone, returning false if there is none
+    pub fn redo(&mut self) -> bool {
+        let redone = self.position + 1 < self.states.len();
+        if redone {
+            self.position += 1;
+        }
+        redone
+    }
+
+    /// Goes to the state after `position` instructions, carrying out any not done yet
+    pub fn seek(&mut self, position: usize) -> Result<()> {
+        self.position = self.position.min(position);
+        while self.position < position && self.step()? {}
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Replay;
+    use crate::day_5::{
+        crane::{CrateMover9000, CrateMover9001},
+        parse_procedure, parse_stacks,
+    };
+
+    const EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+    #[test]
+    fn replay_test() {
+        let procedure = parse_procedure(EXAMPLE).unwrap();
+        let mut replay = Replay::new(&procedure, &CrateMover9000);
+        assert!(!replay.undo());
+        assert_eq!("NDP", replay.stacks().top_crates());
+
+        assert!(replay.step().unwrap());
+        assert_eq!("DCP", replay.stacks().top_crates());
+        assert_eq!(
+            "move 1 from 2 to 1",
+            replay.previous_instruction().unwrap().to_string()
+        );
+        assert!(replay.undo());
+        assert_eq!("NDP", replay.stacks().top_crates());
+        assert!(replay.redo());
+        assert!(!replay.redo());
+        assert_eq!("DCP", replay.stacks().top_crates());
+
+        replay.seek(10).unwrap();
+        assert!(replay.is_finished());
+        assert!(!replay.step().unwrap());
+        assert_eq!("CMZ", replay.stacks().top_crates());
+        assert_eq!(5, replay.states().len());
+
+        // the first stack is left empty
+        replay.seek(2).unwrap();
+        let drawing = replay.stacks().to_string();
+        assert_eq!(
+            vec![
+                "        [Z]",
+                "        [N]",
+                "    [C] [D]",
+                "    [M] [P]",
+                " 1   2   3 "
+            ],
+            drawing.lines().collect::<Vec<_>>()
+        );
+        assert_eq!(drawing, parse_stacks(&drawing).unwrap().to_string());
+    }
+
+    #[test]
+    fn render_test() {
+        let procedure = parse_procedure(EXAMPLE).unwrap();
+        assert_eq!(
+            EXAMPLE.lines().collect::<Vec<_>>(),
+            procedure
+                .to_string()
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(procedure, parse_procedure(&procedure.to_string()).unwrap());
+
+        let mut replay = Replay::new(&procedure, &CrateMover9001);
+        replay.seek(4).unwrap();
+        assert_eq!("MCD", replay.stacks().top_crates());
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::Display;
+
+use super::{crane::CrateMover, MoveInstruction, Procedure, Stacks};
+
+/// Why an instruction can't be carried out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// No stack has this number
+    MissingStack(usize),
+    /// Stacks are numbered from 1
+    NoStackZero,
+    NotEnoughCrates {
+        stack: usize,
+        crates: usize,
+    },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::MissingStack(stack) => write!(f, "there is no stack {stack}"),
+            Problem::NoStackZero => write!(f, "stacks are numbered from 1"),
+            Problem::NotEnoughCrates { stack, crates: 1 } => {
+                write!(f, "stack {stack} only has 1 crate")
+            }
+            Problem::NotEnoughCrates { stack, crates } => {
+                write!(f, "stack {stack} only has {crates} crates")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Problem {}
+
+/// The first instruction of a procedure that can't be carried out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IllegalMove {
+    /// Counting instructions from 1
+    pub step: usize,
+    /// Line of the input, if the procedure was parsed
+    pub line: Option<usize>,
+    pub instruction: MoveInstruction,
+    pub problem: Problem,
+    /// The stacks just before the instruction
+    pub stacks: Stacks,
+}
+
+impl Display for IllegalMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}")?,
+            None => write!(f, "instruction {}", self.step)?,
+        }
+        write!(
+            f,
+            ": can't {}, {}. The stacks were:\n{}",
+            self.instruction, self.problem, self.stacks
+        )
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+impl Procedure {
+    /// Carries out every instruction with `crane`, returning the final stacks or the first
+    /// instruction that can't be carried out
+    pub fn validate(&self, crane: &dyn CrateMover) -> Result<Stacks, IllegalMove> {
+        let mut stacks = self.stacks.clone();
+        for step in 0..self.instructions.len() {
+            self.apply(&mut stacks, step, crane)?;
+        }
+        Ok(stacks)
+    }
+
+    /// Carries out the instruction at index `step` on `stacks`
+    pub fn apply(
+        &self,
+        stacks: &mut Stacks,
+        step: usize,
+        crane: &dyn CrateMover,
+    ) -> Result<(), IllegalMove> {
+        let instruction = self.instructions[step];
+        stacks
+            .apply(&instruction, crane)
+            .map_err(|problem| IllegalMove {
+                step: step + 1,
+                line: self.instruction_lines.get(step).copied(),
+                instruction,
+                problem,
+                stacks: stacks.clone(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IllegalMove, Problem};
+    use crate::day_5::{
+        crane::{CrateMover9000, CrateMover9001},
+        parse_procedure, top_crates_of_stacks, MoveInstruction,
+    };
+
+    const EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 3 from 2 to 1
+move 1 from 4 to 2";
+
+    #[test]
+    fn illegal_move_test() {
+        let procedure = parse_procedure(EXAMPLE).unwrap();
+        let error = procedure.validate(&CrateMover9001).unwrap_err();
+        assert_eq!(
+            IllegalMove {
+                step: 3,
+                line: Some(8),
+                instruction: MoveInstruction {
+                    quantity: 3,
+                    start: 2,
+                    end: 1
+                },
+                problem: Problem::NotEnoughCrates {
+                    stack: 2,
+                    crates: 2
+                },
+                stacks: error.stacks.clone(),
+            },
+            error
+        );
+        assert_eq!(
+            "line 8: can't move 3 from 2 to 1, stack 2 only has 2 crates. The stacks were:
+        [D]
+        [N]
+    [C] [Z]
+    [M] [P]
+ 1   2   3 ",
+            error.to_string()
+        );
+
+        // the 9001 used to panic when taking more crates than there are
+        let error = top_crates_of_stacks(&procedure, &CrateMover9001).unwrap_err();
+        assert!(error.to_string().starts_with("line 8: can't move 3"));
+    }
+
+    #[test]
+    fn missing_stack_test() {
+        let procedure =
+            parse_procedure(&EXAMPLE.replace("move 3 from 2", "move 2 from 2")).unwrap();
+        let error = procedure.validate(&CrateMover9000).unwrap_err();
+        assert_eq!((4, Problem::MissingStack(4)), (error.step, error.problem));
+
+        // moving onto a stack that isn't there doesn't make one up
+        let procedure = parse_procedure("[A]\n 1 \n\nmove 1 from 1 to 99").unwrap();
+        let error = procedure.validate(&CrateMover9000).unwrap_err();
+        assert_eq!(Problem::MissingStack(99), error.problem);
+        // but a numbered column without crates is a stack
+        let procedure = parse_procedure("[A]\n 1   2 \n\nmove 1 from 1 to 2").unwrap();
+        assert_eq!(
+            "A",
+            top_crates_of_stacks(&procedure, &CrateMover9000).unwrap()
+        );
+
+        let mut procedure = parse_procedure("[A]\n 1 \n\nmove 1 from 1 to 0").unwrap();
+        let error = procedure.validate(&CrateMover9000).unwrap_err();
+        assert_eq!(Problem::NoStackZero, error.problem);
+        procedure.instruction_lines.clear();
+        assert!(procedure
+            .validate(&CrateMover9000)
+            .unwrap_err()
+            .to_string()
+            .starts_with("instruction 1: can't move 1 from 1 to 0"));
+    }
+}
@@ -1,41 +1,187 @@
-use itertools::Itertools;
-
-fn main() -> color_eyre::eyre::Result<()> {
-    color_eyre::install()?;
-
-    println!("Day 6");
-    let input = include_str!("input/day_6.txt");
-    println!(
-        "Part 1: {}",
-        first_marker_location(input, Marker::PacketMarker)
-            .ok_or(color_eyre::eyre::eyre!("No marker found"))?
-    );
-    println!(
-        "Part 1: {}",
-        first_marker_location(input, Marker::MessageMarker)
-            .ok_or(color_eyre::eyre::eyre!("No marker found"))?
-    );
-    Ok(())
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use color_eyre::eyre::{eyre, Result};
+use itertools::Either;
+
+use crate::solution::Solution;
+
+pub mod stream;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = String;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne> {
+        first_marker_location(input, Marker::PacketMarker).ok_or_else(|| eyre!("No marker found"))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo> {
+        first_marker_location(input, Marker::MessageMarker).ok_or_else(|| eyre!("No marker found"))
+    }
 }
 
 fn first_marker_location(input: &str, marker: Marker) -> Option<usize> {
-    let size = match marker {
-        Marker::PacketMarker => 4,
-        Marker::MessageMarker => 14,
-    };
-    (0..(input.len() - size))
-        .find(|i| input.chars().skip(*i).take(size).all_unique())
-        .map(|i| i + size)
+    markers(input, marker.size(), Unit::Chars).next()
+}
+
+/// Where every marker of `size` different symbols ends, counting symbols as `unit`. A marker
+/// needs at least one symbol, so there are none of size 0
+pub fn markers(input: &str, size: usize, unit: Unit) -> impl Iterator<Item = usize> + '_ {
+    match unit {
+        Unit::Bytes => Either::Left(Markers::new(input.bytes(), size)),
+        Unit::Chars => Either::Right(Markers::new(input.chars(), size)),
+    }
 }
 
-enum Marker {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
     PacketMarker,
     MessageMarker,
 }
 
+impl Marker {
+    /// Number of different characters in a row that make up the marker
+    pub fn size(self) -> usize {
+        match self {
+            Marker::PacketMarker => 4,
+            Marker::MessageMarker => 14,
+        }
+    }
+}
+
+/// What counts as one symbol of the signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Bytes,
+    Chars,
+}
+
+/// The last `size` symbols of a signal, with how often each of them appears so telling whether
+/// they're all different doesn't need looking at them again
+#[derive(Debug, Clone)]
+pub struct Window<T> {
+    size: usize,
+    symbols: VecDeque<T>,
+    counts: HashMap<T, usize>,
+    /// Number of different symbols in the window more than once
+    repeated: usize,
+}
+
+impl<T: Copy + Eq + Hash> Window<T> {
+    pub fn new(size: usize) -> Self {
+        Window {
+            size,
+            symbols: VecDeque::with_capacity(size + 1),
+            counts: HashMap::new(),
+            repeated: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Slides the window over the next symbol
+    pub fn push(&mut self, symbol: T) {
+        if self.size == 0 {
+            return;
+        }
+        if self.symbols.len() == self.size {
+            let oldest = self.symbols.pop_front().expect("the window is full");
+            let count = self.counts.get_mut(&oldest).expect("symbols are counted");
+            *count -= 1;
+            match *count {
+                0 => {
+                    self.counts.remove(&oldest);
+                }
+                1 => self.repeated -= 1,
+                _ => {}
+            }
+        }
+        self.symbols.push_back(symbol);
+        let count = self.counts.entry(symbol).or_default();
+        *count += 1;
+        if *count == 2 {
+            self.repeated += 1;
+        }
+    }
+
+    /// Whether the window is full of different symbols
+    pub fn is_marker(&self) -> bool {
+        self.size > 0 && self.symbols.len() == self.size && self.repeated == 0
+    }
+
+    /// The symbols in the window, oldest first
+    pub fn symbols(&self) -> impl Iterator<Item = T> + '_ {
+        self.symbols.iter().copied()
+    }
+
+    /// Forgets every symbol seen so far
+    pub fn clear(&mut self) {
+        self.symbols.clear();
+        self.counts.clear();
+        self.repeated = 0;
+    }
+}
+
+/// Iterator over where markers end, counting from 1 for the first symbol
+pub struct Markers<I: Iterator> {
+    symbols: I,
+    window: Window<I::Item>,
+    position: usize,
+}
+
+impl<I> Markers<I>
+where
+    I: Iterator,
+    I::Item: Copy + Eq + Hash,
+{
+    pub fn new(symbols: I, size: usize) -> Self {
+        Markers {
+            symbols,
+            window: Window::new(size),
+            position: 0,
+        }
+    }
+}
+
+impl<I> Iterator for Markers<I>
+where
+    I: Iterator,
+    I::Item: Copy + Eq + Hash,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.size() == 0 {
+            return None;
+        }
+        for symbol in self.symbols.by_ref() {
+            self.window.push(symbol);
+            self.position += 1;
+            if self.window.is_marker() {
+                return Some(self.position);
+            }
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{first_marker_location, Marker};
+    use super::{first_marker_location, markers, Marker, Unit, Window};
 
     #[test]
     fn example_part1() {
@@ -68,4 +214,50 @@ mod tests {
             Some(29)
         );
     }
+
+    #[test]
+    fn all_markers_test() {
+        assert_eq!(
+            vec![3, 4, 7, 8],
+            markers("abcaabcd", 3, Unit::Chars).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            (1..=4).collect::<Vec<_>>(),
+            markers("abcd", 1, Unit::Chars).collect::<Vec<_>>()
+        );
+        // shorter than the window, or no window at all
+        assert_eq!(None, first_marker_location("abc", Marker::PacketMarker));
+        assert_eq!(None, first_marker_location("", Marker::MessageMarker));
+        assert_eq!(None, markers("abc", 0, Unit::Bytes).next());
+    }
+
+    #[test]
+    fn unit_test() {
+        // 'é' is two bytes, 0xC3 0xA9, as is 'ã', 0xC3 0xA3
+        assert_eq!(Some(2), markers("éã", 2, Unit::Chars).next());
+        assert_eq!(Some(2), markers("éã", 2, Unit::Bytes).next());
+        assert_eq!(None, markers("éã", 3, Unit::Chars).next());
+        assert_eq!(Some(4), markers("éã", 3, Unit::Bytes).next());
+        assert_eq!(None, markers("éã", 4, Unit::Bytes).next());
+        assert_eq!(Some(3), markers("aéb", 3, Unit::Chars).next());
+        assert_eq!(Some(3), markers("aéb", 3, Unit::Bytes).next());
+        assert_eq!(
+            vec![3, 4],
+            markers("aéb", 3, Unit::Bytes).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn window_test() {
+        let mut window = Window::new(3);
+        for c in "abca".chars() {
+            window.push(c);
+        }
+        assert_eq!("bca", window.symbols().collect::<String>());
+        assert!(window.is_marker());
+        window.push('c');
+        assert!(!window.is_marker());
+        window.clear();
+        assert_eq!(0, window.symbols().count());
+    }
 }
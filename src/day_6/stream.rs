@@ -0,0 +1,264 @@
+use std::{
+    collections::VecDeque,
+    io::{self, ErrorKind, Read},
+};
+
+use super::Window;
+
+/// Payload is handed out once this many bytes of it are waiting
+pub const CHUNK_SIZE: usize = 4096;
+
+/// Something the decoder found in the stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Bytes before the first marker, starting at index `start` of the stream
+    Skipped { start: usize, bytes: Vec<u8> },
+    /// A marker, ending after `end` bytes of the stream like the puzzle counts them
+    Marker { end: usize, window: Vec<u8> },
+    /// Bytes of the packet started by the last marker, starting at index `start` of the stream
+    Payload { start: usize, bytes: Vec<u8> },
+}
+
+/// Splits a stream into packets started by markers of `size` different bytes, reading it a little
+/// at a time. The bytes of a marker can't be part of the next one, and only the last `size - 1`
+/// bytes plus up to a chunk of payload are kept, so endless streams can be decoded
+pub struct Decoder<R> {
+    reader: R,
+    buffer: Box<[u8]>,
+    /// The part of `buffer` read but not decoded yet
+    unread: std::ops::Range<usize>,
+    window: Window<u8>,
+    /// Bytes read since the last event, some of which might start the next marker
+    pending: VecDeque<u8>,
+    /// Index of the first pending byte in the stream
+    pending_start: usize,
+    chunk_size: usize,
+    markers: usize,
+    events: VecDeque<Event>,
+    finished: bool,
+}
+
+impl<R: Read> Decoder<R> {
+    pub fn new(reader: R, size: usize) -> Self {
+        Decoder {
+            reader,
+            buffer: vec![0; CHUNK_SIZE].into_boxed_slice(),
+            unread: 0..0,
+            window: Window::new(size),
+            pending: VecDeque::new(),
+            pending_start: 0,
+            chunk_size: CHUNK_SIZE,
+            markers: 0,
+            events: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Hands out payload in pieces of up to `chunk_size` bytes instead of [`CHUNK_SIZE`]
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Number of markers found so far
+    pub fn markers(&self) -> usize {
+        self.markers
+    }
+
+    /// Bytes held back so far, at most a chunk and a marker
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn decode(&mut self, byte: u8) {
+        self.window.push(byte);
+        self.pending.push_back(byte);
+        let size = self.window.size();
+        if self.window.is_marker() {
+            self.flush(self.pending.len() - size);
+            let window = self.pending.drain(..).collect();
+            self.pending_start += size;
+            self.markers += 1;
+            self.events.push_back(Event::Marker {
+                end: self.pending_start,
+                window,
+            });
+            self.window.clear();
+        } else if self.pending.len() >= self.chunk_size + size.saturating_sub(1) {
+            self.flush(self.chunk_size);
+        }
+    }
+
+    /// Hands out the first `len` pending bytes
+    fn flush(&mut self, len: usize) {
+        if len == 0 {
+            return;
+        }
+        let bytes = self.pending.drain(..len).collect();
+        let start = self.pending_start;
+        self.pending_start += len;
+        self.events.push_back(if self.markers == 0 {
+            Event::Skipped { start, bytes }
+        } else {
+            Event::Payload { start, bytes }
+        });
+    }
+
+    fn fill(&mut self) -> io::Result<usize> {
+        loop {
+            match self.reader.read(&mut self.buffer) {
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for Decoder<R> {
+    type Item = io::Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.events.is_empty() && !self.finished {
+            if self.unread.is_empty() {
+                match self.fill() {
+                    Ok(0) => {
+                        self.finished = true;
+                        self.flush(self.pending.len());
+                    }
+                    Ok(read) => self.unread = 0..read,
+                    Err(e) => {
+                        self.finished = true;
+                        return Some(Err(e));
+                    }
+                }
+                continue;
+            }
+            let byte = self.buffer[self.unread.start];
+            self.unread.start += 1;
+            self.decode(byte);
+        }
+        self.events.pop_front().map(Ok)
+    }
+}
+
+/// A marker and everything up to the next one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    /// Bytes of the stream up to the end of the marker
+    pub end: usize,
+    pub marker: Vec<u8>,
+    pub payload: Vec<u8>,
+}
+
+/// Reads a whole stream, returning its packets and dropping anything before the first marker
+pub fn packets(reader: impl Read, size: usize) -> io::Result<Vec<Packet>> {
+    let mut packets = Vec::<Packet>::new();
+    for event in Decoder::new(reader, size) {
+        match event? {
+            Event::Skipped { .. } => {}
+            Event::Marker { end, window } => packets.push(Packet {
+                end,
+                marker: window,
+                payload: Vec::new(),
+            }),
+            Event::Payload { bytes, .. } => packets
+                .last_mut()
+                .expect("payload comes after a marker")
+                .payload
+                .extend(bytes),
+        }
+    }
+    Ok(packets)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::{packets, Decoder, Event, Packet};
+    use crate::day_6::Marker;
+
+    #[test]
+    fn events_test() {
+        let events = Decoder::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes(), 4)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            Event::Skipped {
+                start: 0,
+                bytes: b"mjq".to_vec()
+            },
+            events[0]
+        );
+        // the puzzle's answer
+        assert_eq!(
+            Event::Marker {
+                end: 7,
+                window: b"jpqm".to_vec()
+            },
+            events[1]
+        );
+        assert_eq!(
+            Event::Marker {
+                end: 11,
+                window: b"gblj".to_vec()
+            },
+            events[2]
+        );
+    }
+
+    #[test]
+    fn packets_test() {
+        let size = Marker::PacketMarker.size();
+        let packets = packets("aaabcdhello!wxyzab".as_bytes(), size).unwrap();
+        let packet = |end, marker: &[u8], payload: &[u8]| Packet {
+            end,
+            marker: marker.to_vec(),
+            payload: payload.to_vec(),
+        };
+        // `hello` repeats an `l`, and `wxyz` can't reuse the `w` of `lo!w`
+        assert_eq!(
+            vec![
+                packet(6, b"abcd", b"hel"),
+                packet(13, b"lo!w", b""),
+                packet(17, b"xyza", b"b")
+            ],
+            packets
+        );
+    }
+
+    /// Repeats `aab` forever, with a marker every so often
+    struct Endless(usize);
+
+    impl Read for Endless {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            for byte in buf.iter_mut() {
+                self.0 += 1;
+                *byte = if self.0.is_multiple_of(10_000) {
+                    b'c'
+                } else {
+                    b"aab"[self.0 % 3]
+                };
+            }
+            Ok(buf.len())
+        }
+    }
+
+    #[test]
+    fn endless_stream_test() {
+        let mut decoder = Decoder::new(Endless(0), 3).with_chunk_size(100);
+        let mut payload = 0;
+        let mut markers = 0;
+        while markers < 20 {
+            match decoder.next().unwrap().unwrap() {
+                Event::Marker { .. } => markers += 1,
+                Event::Payload { bytes, .. } | Event::Skipped { bytes, .. } => {
+                    assert!(bytes.len() <= 100);
+                    payload += bytes.len();
+                }
+            }
+            assert!(decoder.pending() < 100 + 3);
+        }
+        assert!(payload > 100_000);
+    }
+}
@@ -2,36 +2,52 @@ use std::str::FromStr;
 
 use color_eyre::eyre::eyre;
 use color_eyre::eyre::Result;
-use itertools::Itertools;
+
+use crate::solution::Solution;
+
+use self::shell::Shell;
+
+pub mod shell;
 
 const TOTAL_FILE_SPACE: u64 = 70000000;
 const REQUIRED_FILE_SPACE: u64 = 30000000;
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+pub struct Day7;
 
-    println!("Day 7");
-    let input = include_str!("input/day_7.txt");
-    println!("Part 1: {}", total_size_of_at_most(100_000, input)?);
-    println!("Part 2: {}", size_of_smallest_dir_to_delete(input)?);
-    Ok(())
-}
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = FileSystem;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        FileSystem::from_str(input)
+    }
+
+    fn part_one(system: &Self::Input) -> Result<Self::PartOne> {
+        Ok(total_size_of_at_most(100_000, system))
+    }
 
-fn total_size_of_at_most(limit: u64, input: &str) -> Result<u64> {
-    let system = FileSystem::from_str(input)?;
+    fn part_two(system: &Self::Input) -> Result<Self::PartTwo> {
+        size_of_smallest_dir_to_delete(system)
+    }
+}
 
-    Ok(system
+/// Sum of the sizes of the directories of at most `limit`, counting nested ones again
+pub fn total_size_of_at_most(limit: u64, system: &FileSystem) -> u64 {
+    system
         .dirs()
         .iter()
         .filter(|dir| dir.size() <= limit)
-        .fold(0, |sum, dir2| sum + dir2.size()))
+        .fold(0, |sum, dir2| sum + dir2.size())
 }
 
-fn size_of_smallest_dir_to_delete(input: &str) -> Result<u64> {
-    let system = FileSystem::from_str(input)?;
-
+/// Size of the smallest directory freeing up enough space for the update
+pub fn size_of_smallest_dir_to_delete(system: &FileSystem) -> Result<u64> {
     let used_space: u64 = system.root_size();
-    let required_space = REQUIRED_FILE_SPACE as i64 - (TOTAL_FILE_SPACE - used_space) as i64;
+    let required_space =
+        REQUIRED_FILE_SPACE as i128 - (TOTAL_FILE_SPACE as i128 - used_space as i128);
 
     if required_space <= 0 {
         return Err(eyre!("There is already enough space"));
@@ -46,18 +62,37 @@ fn size_of_smallest_dir_to_delete(input: &str) -> Result<u64> {
         .ok_or(eyre!("No dir found for deletion?"))
 }
 
+/// How much bigger or smaller a directory gets
+#[derive(Debug, Clone, Copy)]
+enum SizeChange {
+    Grow(u64),
+    Shrink(u64),
+}
+
+impl SizeChange {
+    fn apply(self, size: u64) -> Result<u64> {
+        match self {
+            SizeChange::Grow(amount) => size
+                .checked_add(amount)
+                .ok_or_else(|| eyre!("Directory size would overflow")),
+            SizeChange::Shrink(amount) => size
+                .checked_sub(amount)
+                .ok_or_else(|| eyre!("Directory size would become negative")),
+        }
+    }
+}
+
+/// Index of an entry in the `data` of a [`FileSystem`]
 type FileId = usize;
 #[derive(Debug)]
 enum FileType {
     Directory {
-        id: FileId,
         parent: Option<FileId>,
         name: String,
         content: Vec<FileId>,
         total_size: u64,
     },
     File {
-        id: FileId,
         parent: Option<FileId>,
         name: String,
         size: u64,
@@ -65,7 +100,7 @@ enum FileType {
 }
 
 #[derive(Debug)]
-struct FileSystem {
+pub struct FileSystem {
     data: Vec<FileType>,
 }
 
@@ -74,13 +109,11 @@ impl FileType {
         match self {
             FileType::Directory {
                 name: _,
-                id: _,
                 parent: _,
                 content: _,
                 total_size,
             } => *total_size,
             FileType::File {
-                id: _,
                 parent: _,
                 name: _,
                 size,
@@ -88,36 +121,16 @@ impl FileType {
         }
     }
 
-    fn id(&self) -> FileId {
-        match self {
-            FileType::Directory {
-                name: _,
-                id,
-                parent: _,
-                content: _,
-                total_size: _,
-            } => *id,
-            FileType::File {
-                id,
-                parent: _,
-                name: _,
-                size: _,
-            } => *id,
-        }
-    }
-
     fn parent(&self) -> FileId {
         match self {
             FileType::Directory {
                 name: _,
                 parent,
-                id: _,
                 content: _,
                 total_size: _,
             } => parent.unwrap_or(ROOT_FILE_ID),
             FileType::File {
                 parent,
-                id: _,
                 name: _,
                 size: _,
             } => parent.unwrap_or(ROOT_FILE_ID),
@@ -133,43 +146,11 @@ impl FileSystem {
                 name: "/".to_string(),
                 content: Vec::new(),
                 parent: None,
-                id: ROOT_FILE_ID,
                 total_size: 0,
             }],
         }
     }
 
-    fn get_child_directory_with_name(&self, parent: FileId, name: &str) -> Option<FileId> {
-        self.data
-            .get(parent)
-            .and_then(|f| match f {
-                FileType::Directory {
-                    id: _,
-                    parent: _,
-                    name: _,
-                    content,
-                    total_size: _,
-                } => Some(content),
-                _ => None,
-            })
-            .and_then(|children| {
-                children
-                    .iter()
-                    .filter_map(|id| self.data.get(*id))
-                    .find(|f| match f {
-                        FileType::Directory {
-                            id: _,
-                            parent: _,
-                            content: _,
-                            total_size: _,
-                            name: file_name,
-                        } => file_name == name,
-                        _ => false,
-                    })
-            })
-            .map(|f| f.id())
-    }
-
     fn get_parent_of_file(&self, file_id: FileId) -> Option<FileId> {
         self.data.get(file_id).map(|f| f.parent())
     }
@@ -177,7 +158,6 @@ impl FileSystem {
     fn add_file(&mut self, parent: FileId, name: &str, size: u64) -> Result<()> {
         self.add_child_to_directory(parent, self.data.len(), size)?;
         self.data.push(FileType::File {
-            id: self.data.len(),
             parent: Some(parent),
             name: name.to_string(),
             size,
@@ -188,7 +168,6 @@ impl FileSystem {
     fn add_empty_directory(&mut self, parent: FileId, name: &str) -> Result<()> {
         self.add_child_to_directory(parent, self.data.len(), 0)?;
         self.data.push(FileType::Directory {
-            id: self.data.len(),
             parent: Some(parent),
             name: name.to_string(),
             content: Vec::new(),
@@ -197,9 +176,11 @@ impl FileSystem {
         Ok(())
     }
 
+    /// Directories still in the tree, as removed entries stay in the arena
     fn dirs(&self) -> Vec<&FileType> {
-        self.data
-            .iter()
+        self.walk(ROOT_FILE_ID)
+            .into_iter()
+            .map(|id| &self.data[id])
             .filter(|f| match f {
                 FileType::File { .. } => false,
                 FileType::Directory { .. } => true,
@@ -207,76 +188,248 @@ impl FileSystem {
             .collect::<Vec<_>>()
     }
 
+    /// `id` and everything under it, parents before their content
+    fn walk(&self, id: FileId) -> Vec<FileId> {
+        let mut found = vec![id];
+        let mut i = 0;
+        while let Some(next) = found.get(i) {
+            found.extend_from_slice(self.content(*next));
+            i += 1;
+        }
+        found
+    }
+
+    fn content(&self, id: FileId) -> &[FileId] {
+        match self.data.get(id) {
+            Some(FileType::Directory { content, .. }) => content,
+            _ => &[],
+        }
+    }
+
+    fn name(&self, id: FileId) -> &str {
+        match &self.data[id] {
+            FileType::Directory { name, .. } | FileType::File { name, .. } => name,
+        }
+    }
+
+    fn is_directory(&self, id: FileId) -> bool {
+        matches!(self.data.get(id), Some(FileType::Directory { .. }))
+    }
+
+    fn get_child_with_name(&self, parent: FileId, name: &str) -> Option<FileId> {
+        self.content(parent)
+            .iter()
+            .copied()
+            .find(|child| self.name(*child) == name)
+    }
+
+    /// Follows a path like `a/b`, `../c` or `/d` from `from`
+    pub fn resolve(&self, from: FileId, path: &str) -> Option<FileId> {
+        let start = if path.starts_with('/') {
+            ROOT_FILE_ID
+        } else {
+            from
+        };
+        path.split('/')
+            .filter(|part| !part.is_empty() && *part != ".")
+            .try_fold(start, |current, part| match part {
+                ".." => self.get_parent_of_file(current),
+                _ => self.get_child_with_name(current, part),
+            })
+    }
+
+    /// Full path of an entry, like `/a/e`
+    pub fn path(&self, id: FileId) -> String {
+        let mut parts = Vec::new();
+        let mut current = id;
+        while current != ROOT_FILE_ID {
+            parts.push(self.name(current));
+            current = self.data[current].parent();
+        }
+        parts.reverse();
+        format!("/{}", parts.join("/"))
+    }
+
+    /// Size of an entry, counting everything in it for directories
+    pub fn size_of(&self, id: FileId) -> Option<u64> {
+        self.data.get(id).map(FileType::size)
+    }
+
+    /// Creates an empty directory, failing if there already is something with that name
+    pub fn make_directory(&mut self, parent: FileId, name: &str) -> Result<FileId> {
+        self.check_new_name(parent, name)?;
+        self.add_empty_directory(parent, name)?;
+        Ok(self.data.len() - 1)
+    }
+
+    /// Creates a file, or changes the size of an existing one if `size` is given
+    pub fn touch(&mut self, parent: FileId, name: &str, size: Option<u64>) -> Result<FileId> {
+        let Some(existing) = self.get_child_with_name(parent, name) else {
+            self.check_new_name(parent, name)?;
+            self.add_file(parent, name, size.unwrap_or(0))?;
+            return Ok(self.data.len() - 1);
+        };
+        let Some(size) = size else {
+            return Ok(existing);
+        };
+        match &mut self.data[existing] {
+            FileType::File { size: old, .. } => {
+                let change = if size >= *old {
+                    SizeChange::Grow(size - *old)
+                } else {
+                    SizeChange::Shrink(*old - size)
+                };
+                self.update_parent_size(parent, change)?;
+                if let FileType::File { size: old, .. } = &mut self.data[existing] {
+                    *old = size;
+                }
+                Ok(existing)
+            }
+            FileType::Directory { .. } => Err(eyre!("{name} is a directory")),
+        }
+    }
+
+    /// Takes an entry out of the tree, with everything in it
+    pub fn remove(&mut self, id: FileId, recursive: bool) -> Result<()> {
+        if id == ROOT_FILE_ID {
+            return Err(eyre!("Cannot remove /"));
+        }
+        if self.is_directory(id) && !recursive {
+            return Err(eyre!("{} is a directory", self.path(id)));
+        }
+        self.detach(id)
+    }
+
+    /// Moves an entry into another directory under a new name, keeping everything in it
+    pub fn move_to(&mut self, id: FileId, parent: FileId, name: &str) -> Result<()> {
+        if id == ROOT_FILE_ID {
+            return Err(eyre!("Cannot move /"));
+        }
+        if self.walk(id).contains(&parent) {
+            return Err(eyre!("Cannot move {} into itself", self.path(id)));
+        }
+        if self.get_child_with_name(parent, name) == Some(id) {
+            return Ok(());
+        }
+        self.check_new_name(parent, name)?;
+
+        self.detach(id)?;
+        match &mut self.data[id] {
+            FileType::Directory {
+                parent: p_id,
+                name: n,
+                ..
+            }
+            | FileType::File {
+                parent: p_id,
+                name: n,
+                ..
+            } => {
+                *p_id = Some(parent);
+                *n = name.to_string();
+            }
+        }
+        let size = self.data[id].size();
+        self.add_child_to_directory(parent, id, size)
+    }
+
+    /// Every directory under `id` (included) with its size, contents before the directory
+    /// like `du` lists them
+    pub fn disk_usage(&self, id: FileId) -> Vec<(String, u64)> {
+        self.walk(id)
+            .into_iter()
+            .rev()
+            .filter(|id| self.is_directory(*id))
+            .map(|id| (self.path(id), self.data[id].size()))
+            .collect()
+    }
+
+    fn check_new_name(&self, parent: FileId, name: &str) -> Result<()> {
+        if name.is_empty() || name.contains('/') || name == "." || name == ".." {
+            return Err(eyre!("Invalid name {name:?}"));
+        }
+        if !self.is_directory(parent) {
+            return Err(eyre!("{} is not a directory", self.path(parent)));
+        }
+        if self.get_child_with_name(parent, name).is_some() {
+            let path = self.path(parent);
+            return Err(eyre!(
+                "{}/{name} already exists",
+                path.trim_end_matches('/')
+            ));
+        }
+        Ok(())
+    }
+
+    /// Takes an entry out of its parent's content, which no longer counts its size
+    fn detach(&mut self, id: FileId) -> Result<()> {
+        let parent = self
+            .get_parent_of_file(id)
+            .ok_or_else(|| eyre!("Entry not found"))?;
+        let size = self.data[id].size();
+        self.update_parent_size(parent, SizeChange::Shrink(size))?;
+        match self.data.get_mut(parent) {
+            Some(FileType::Directory { content, .. }) => content.retain(|child| *child != id),
+            _ => return Err(eyre!("Found file with parent as file!")),
+        }
+        Ok(())
+    }
+
     fn add_child_to_directory(
         &mut self,
         parent: FileId,
         child: FileId,
         size_increase: u64,
     ) -> Result<()> {
-        let mut updated_item_parent = None;
-
         match self
             .data
-            .get_mut(parent)
+            .get(parent)
             .ok_or(eyre!("Could not add child because parent not found"))?
         {
-            FileType::Directory {
-                id: _,
-                parent: p_id,
-                name: _,
-                content,
-                total_size,
-            } => {
-                *total_size += size_increase;
-                content.push(child);
-                if p_id.is_some() && size_increase > 0 {
-                    updated_item_parent = Some(p_id.unwrap());
-                }
-            }
+            FileType::Directory { .. } => {}
             _ => return Err(eyre!("Cannot add child to a file")),
         };
-        if let Some(found_item_parent) = updated_item_parent {
-            self.update_parent_size(found_item_parent, size_increase)?;
-        };
+        self.update_parent_size(parent, SizeChange::Grow(size_increase))?;
+        if let Some(FileType::Directory { content, .. }) = self.data.get_mut(parent) {
+            content.push(child);
+        }
 
         Ok(())
     }
 
-    fn update_parent_size(&mut self, id: usize, size_increase: u64) -> Result<()> {
-        let mut parent_of_updated = None;
-
-        if let Some(f) = self.data.get_mut(id) {
-            match f {
-                FileType::Directory {
-                    id: _,
-                    parent,
-                    name: _,
-                    content: _,
-                    total_size,
-                } => {
-                    *total_size += size_increase;
-                    if parent.is_some() {
-                        parent_of_updated = Some(parent.unwrap());
-                    }
+    /// Changes the size of the directory and all the directories it is in. Every size is checked
+    /// first, so if one of them can't take the change they are all left as they were
+    fn update_parent_size(&mut self, id: usize, change: SizeChange) -> Result<()> {
+        let mut directories = Vec::new();
+        let mut current = Some(id);
+        while let Some(found) = current {
+            match self.data.get(found) {
+                Some(FileType::Directory {
+                    parent, total_size, ..
+                }) => {
+                    change.apply(*total_size)?;
+                    directories.push(found);
+                    current = *parent;
+                }
+                Some(FileType::File { .. }) => {
+                    return Err(eyre!("Found file with parent as file!"))
                 }
-                FileType::File {
-                    id: _,
-                    parent: _,
-                    name: _,
-                    size: _,
-                } => return Err(eyre!("Found file with parent as file!")),
+                None => current = None,
             }
         }
 
-        if let Some(found_item_parent) = parent_of_updated {
-            self.update_parent_size(found_item_parent, size_increase)?;
+        for directory in directories {
+            if let FileType::Directory { total_size, .. } = &mut self.data[directory] {
+                *total_size = change.apply(*total_size)?;
+            }
         }
 
         Ok(())
     }
 
-    fn root_size(&self) -> u64 {
-        self.data.get(0).unwrap().size()
+    /// Space used on the whole disk
+    pub fn root_size(&self) -> u64 {
+        self.data.first().unwrap().size()
     }
 }
 
@@ -284,63 +437,23 @@ impl FromStr for FileSystem {
     type Err = color_eyre::eyre::Report;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut f = FileSystem::new();
-
-        let mut current_dir = ROOT_FILE_ID;
+        let mut shell = Shell::new(FileSystem::new());
         for line in s
             .lines()
             .skip_while(|l| !l.eq_ignore_ascii_case("$ cd /"))
             .skip(1)
         {
-            // dbg!(&f);
-            if line.starts_with('$') {
-                // command
-                if line.len() == 4 {
-                    // '$ ls' command
-                    continue;
-                } else {
-                    let (command, arg) = line
-                        .split_whitespace()
-                        .skip(1)
-                        .next_tuple()
-                        .ok_or(eyre!("Malformed command"))?;
-
-                    match command {
-                        "cd" => {
-                            if arg == ".." {
-                                current_dir = f
-                                    .get_parent_of_file(current_dir)
-                                    .ok_or(eyre!("Parent not found"))?;
-                            } else {
-                                current_dir = f
-                                    .get_child_directory_with_name(current_dir, arg)
-                                    .ok_or(eyre!("Attempting to cd to a non valid directory"))?;
-                            }
-                        }
-                        _ => return Err(eyre!("Unrecognized command")),
-                    }
-                }
-            } else {
-                // description
-                let (first, second) = line
-                    .split_once(' ')
-                    .ok_or(eyre!("Ill formed file item descriptor"))?;
-
-                if first == "dir" {
-                    f.add_empty_directory(current_dir, second)?;
-                } else {
-                    f.add_file(current_dir, second, first.parse::<u64>()?)?;
-                }
-            }
+            shell.execute(line)?;
         }
-
-        Ok(f)
+        Ok(shell.into_system())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{size_of_smallest_dir_to_delete, total_size_of_at_most};
+    use std::str::FromStr;
+
+    use super::{size_of_smallest_dir_to_delete, total_size_of_at_most, FileSystem};
 
     #[test]
     fn example_part1() {
@@ -368,7 +481,8 @@ $ ls
 5626152 d.ext
 7214296 k";
 
-        assert_eq!(total_size_of_at_most(100_000, input).unwrap(), 95437);
+        let system = FileSystem::from_str(input).unwrap();
+        assert_eq!(total_size_of_at_most(100_000, &system), 95437);
     }
 
     #[test]
@@ -397,6 +511,7 @@ $ ls
 5626152 d.ext
 7214296 k";
 
-        assert_eq!(size_of_smallest_dir_to_delete(input).unwrap(), 24933642);
+        let system = FileSystem::from_str(input).unwrap();
+        assert_eq!(size_of_smallest_dir_to_delete(&system).unwrap(), 24933642);
     }
 }
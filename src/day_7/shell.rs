@@ -0,0 +1,282 @@
+use color_eyre::eyre::{eyre, Result};
+
+use super::{FileId, FileSystem, ROOT_FILE_ID};
+
+/// Runs shell commands one at a time against a [`FileSystem`], so the disk can be looked at
+/// between them. Besides `cd` and `ls` it knows
+/// - `mkdir PATH`
+/// - `touch PATH [SIZE]`, creating a file or changing its size
+/// - `rm [-r] PATH`
+/// - `mv FROM TO`, into `TO` if it is a directory
+/// - `du [PATH]`
+///
+/// Lines not starting with `$` are the listing of the directory after an `ls`, and are ignored
+/// after other commands as they are only their recorded output
+pub struct Shell {
+    system: FileSystem,
+    current_dir: FileId,
+    listing: bool,
+}
+
+impl Shell {
+    pub fn new(system: FileSystem) -> Self {
+        Shell {
+            system,
+            current_dir: ROOT_FILE_ID,
+            listing: false,
+        }
+    }
+
+    pub fn system(&self) -> &FileSystem {
+        &self.system
+    }
+
+    pub fn into_system(self) -> FileSystem {
+        self.system
+    }
+
+    /// Path of the directory commands run in
+    pub fn current_dir(&self) -> String {
+        self.system.path(self.current_dir)
+    }
+
+    /// Runs a line of a transcript, returning what the command prints
+    pub fn execute(&mut self, line: &str) -> Result<Vec<String>> {
+        let Some(command) = line.strip_prefix('$') else {
+            if self.listing && !line.trim().is_empty() {
+                self.list_entry(line)?;
+            }
+            return Ok(Vec::new());
+        };
+
+        let mut args = command.split_whitespace();
+        let name = args.next().ok_or_else(|| eyre!("Malformed command"))?;
+        let args = args.collect::<Vec<_>>();
+        self.listing = name == "ls";
+        match (name, args.as_slice()) {
+            ("ls", []) => {}
+            ("cd", [path]) => {
+                let dir = self.find(path)?;
+                if !self.system.is_directory(dir) {
+                    return Err(eyre!("{path} is not a directory"));
+                }
+                self.current_dir = dir;
+            }
+            ("mkdir", [path]) => {
+                let (parent, name) = self.find_parent(path)?;
+                self.system.make_directory(parent, name)?;
+            }
+            ("touch", [path]) => {
+                let (parent, name) = self.find_parent(path)?;
+                self.system.touch(parent, name, None)?;
+            }
+            ("touch", [path, size]) => {
+                let (parent, name) = self.find_parent(path)?;
+                self.system.touch(parent, name, Some(size.parse()?))?;
+            }
+            ("rm", [path]) => self.system.remove(self.find(path)?, false)?,
+            ("rm", ["-r", path]) => {
+                let id = self.find(path)?;
+                if self.system.walk(id).contains(&self.current_dir) {
+                    return Err(eyre!("Cannot remove {path} while in it"));
+                }
+                self.system.remove(id, true)?
+            }
+            ("mv", [from, to]) => {
+                let id = self.find(from)?;
+                let (parent, name) = match self.system.resolve(self.current_dir, to) {
+                    Some(dir) if self.system.is_directory(dir) => (dir, self.system.name(id)),
+                    _ => self.find_parent(to)?,
+                };
+                let name = name.to_string();
+                self.system.move_to(id, parent, &name)?;
+            }
+            ("du", []) => return Ok(self.disk_usage(self.current_dir)),
+            ("du", [path]) => return Ok(self.disk_usage(self.find(path)?)),
+            _ => return Err(eyre!("Unrecognized command {command:?}")),
+        }
+        Ok(Vec::new())
+    }
+
+    /// Adds a `dir NAME` or `SIZE NAME` line of `ls` output, unless it was already listed
+    fn list_entry(&mut self, line: &str) -> Result<()> {
+        let (first, name) = line
+            .split_once(' ')
+            .ok_or_else(|| eyre!("Ill formed file item descriptor"))?;
+        if first != "dir" {
+            self.system
+                .touch(self.current_dir, name, Some(first.parse()?))?;
+        } else if self
+            .system
+            .get_child_with_name(self.current_dir, name)
+            .is_none()
+        {
+            self.system.make_directory(self.current_dir, name)?;
+        }
+        Ok(())
+    }
+
+    fn disk_usage(&self, id: FileId) -> Vec<String> {
+        self.system
+            .disk_usage(id)
+            .into_iter()
+            .map(|(path, size)| format!("{size}\t{path}"))
+            .collect()
+    }
+
+    fn find(&self, path: &str) -> Result<FileId> {
+        self.system
+            .resolve(self.current_dir, path)
+            .ok_or_else(|| eyre!("No such file or directory: {path}"))
+    }
+
+    /// The directory an entry at `path` would be in, and its name
+    fn find_parent<'a>(&self, path: &'a str) -> Result<(FileId, &'a str)> {
+        match path.trim_end_matches('/').rsplit_once('/') {
+            Some(("", name)) => Ok((ROOT_FILE_ID, name)),
+            Some((dir, name)) => Ok((self.find(dir)?, name)),
+            None => Ok((self.current_dir, path)),
+        }
+    }
+}
+
+impl Default for Shell {
+    /// A shell on an empty disk
+    fn default() -> Self {
+        Shell::new(FileSystem::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Shell;
+    use crate::day_7::{size_of_smallest_dir_to_delete, total_size_of_at_most, FileSystem};
+
+    const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    fn run(shell: &mut Shell, commands: &str) -> Vec<String> {
+        commands
+            .lines()
+            .flat_map(|line| shell.execute(line).unwrap())
+            .collect()
+    }
+
+    fn size(shell: &Shell, path: &str) -> u64 {
+        let system = shell.system();
+        system.size_of(system.resolve(0, path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn commands_test() {
+        let mut shell = Shell::new(FileSystem::from_str(EXAMPLE).unwrap());
+        assert_eq!("/", shell.current_dir());
+        assert_eq!(48381165, size(&shell, "/"));
+
+        run(
+            &mut shell,
+            "$ cd /a\n$ mkdir x\n$ touch x/y 1000\n$ touch f 116",
+        );
+        assert_eq!(1000, size(&shell, "/a/x"));
+        assert_eq!(94853 - 29000 + 1000, size(&shell, "/a"));
+        assert_eq!(48381165 - 29000 + 1000, size(&shell, "/"));
+
+        // e and everything in it go to d
+        run(&mut shell, "$ mv e ../d\n$ mv f /d/renamed");
+        assert_eq!(584, size(&shell, "/d/e"));
+        assert_eq!(116, size(&shell, "/d/renamed"));
+        assert_eq!(2557 + 62596 + 1000, size(&shell, "/a"));
+        assert_eq!(24933642 + 584 + 116, size(&shell, "/d"));
+        assert!(shell.system().resolve(0, "/a/e").is_none());
+
+        run(&mut shell, "$ rm -r /d\n$ rm /b.txt");
+        assert_eq!(8504156 + 66153, size(&shell, "/"));
+        assert_eq!(
+            vec!["1000\t/a/x", "66153\t/a", "8570309\t/"],
+            run(&mut shell, "$ du /")
+        );
+        // the analyses only look at what is left
+        assert_eq!(66153 + 1000, total_size_of_at_most(100_000, shell.system()));
+        assert!(size_of_smallest_dir_to_delete(shell.system()).is_err());
+    }
+
+    #[test]
+    fn listing_test() {
+        let mut shell = Shell::new(FileSystem::from_str(EXAMPLE).unwrap());
+        // listing a directory again only picks up what changed, and `du` output is ignored
+        run(
+            &mut shell,
+            "$ cd d\n$ ls\n4060174 j\n1 k\n$ du\n24933642\t/d",
+        );
+        assert_eq!(4060174 + 8033020 + 5626152 + 1, size(&shell, "/d"));
+        let system = shell.system();
+        assert_eq!(5, system.walk(system.resolve(0, "/d").unwrap()).len());
+    }
+
+    #[test]
+    fn errors_test() {
+        let mut shell = Shell::new(FileSystem::from_str(EXAMPLE).unwrap());
+        shell.execute("$ cd /d").unwrap();
+        for command in [
+            "$ cd j",
+            "$ cd nowhere",
+            "$ mkdir k",
+            "$ mkdir /nowhere/x",
+            "$ rm /a",
+            "$ rm -r /",
+            "$ rm -r .",
+            "$ mv /a /a/e",
+            "$ mv /b.txt /a/f",
+            "$ touch /a 3",
+            "$ cp /a /b",
+        ] {
+            assert!(shell.execute(command).is_err(), "{command}");
+        }
+        assert_eq!(48381165, size(&shell, "/"));
+
+        // sizes that don't fit leave every size as it was
+        let mut shell = Shell::default();
+        run(
+            &mut shell,
+            "$ mkdir d\n$ ls\n9223372036854775807 a\n9223372036854775807 b",
+        );
+        assert!(shell.execute("9 c").is_err());
+        assert!(shell.execute("$ touch d/e 18446744073709551615").is_err());
+        assert!(shell.execute("$ touch a 9223372036854775809").is_err());
+        assert_eq!(9223372036854775807, size(&shell, "/a"));
+        assert_eq!(u64::MAX - 1, size(&shell, "/"));
+        assert_eq!(0, size(&shell, "/d"));
+        assert!(shell.system().resolve(0, "c").is_none());
+        assert!(shell.system().resolve(0, "d/e").is_none());
+
+        // sizes over i64::MAX can be taken away again
+        let mut shell = Shell::default();
+        run(&mut shell, "$ ls\n9223372036854775808 big\n$ rm big");
+        assert_eq!(0, size(&shell, "/"));
+        run(&mut shell, "$ touch a 18446744073709551615\n$ touch a 1");
+        assert_eq!(1, size(&shell, "/"));
+    }
+}
@@ -1,22 +1,35 @@
-use std::{collections::HashSet, f64::consts::SQRT_2};
+use std::collections::HashSet;
 
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 
-fn main() -> Result<()> {
-    color_eyre::install()?;
+use crate::{point::Vec2, solution::Solution};
 
-    let input = include_str!("input/day_9.txt");
-    println!("Part 1: {}", unique_tail_positions(input, 2)?);
-    println!("Part 2: {}", unique_tail_positions(input, 10)?);
-    Ok(())
-}
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
 
-fn unique_tail_positions(input: &str, rope_size: usize) -> Result<usize> {
-    let movements = parse_movements(input)?;
+    type Input = Vec<Movement>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_movements(input)
+    }
+
+    fn part_one(movements: &Self::Input) -> Result<Self::PartOne> {
+        unique_tail_positions(movements, 2)
+    }
+
+    fn part_two(movements: &Self::Input) -> Result<Self::PartTwo> {
+        unique_tail_positions(movements, 10)
+    }
+}
+
+fn unique_tail_positions(movements: &[Movement], rope_size: usize) -> Result<usize> {
     let mut rope = Rope {
-        knots: (0..rope_size).map(|_| (0, 0).into()).collect_vec(),
+        knots: vec![MapPos::ZERO; rope_size],
     };
     // dbg!(&rope);
     // visualize(&rope);
@@ -38,10 +51,10 @@ fn unique_tail_positions(input: &str, rope_size: usize) -> Result<usize> {
     Ok(tail_positions.len())
 }
 
-fn visualize(rope: &Rope) {
+pub fn visualize(rope: &Rope) {
     for p in (-5..5).rev() {
         for q in -5..5 {
-            let current: MapPos = (q, p).into();
+            let current = MapPos::new(q, p);
             let char = if let Some((pos, _)) = rope.knots.iter().find_position(|r| **r == current) {
                 if pos == 0 {
                     "H".to_owned()
@@ -87,38 +100,27 @@ fn parse_movements(input: &str) -> Result<Vec<Movement>> {
 
 fn move_rope(rope: &mut Rope, direction: Direction) -> Result<()> {
     let first_knot = rope.knots.first_mut().ok_or(eyre!("Rope is empty!"))?;
-    match direction {
-        Direction::Up => first_knot.y += 1,
-        Direction::Down => first_knot.y -= 1,
-        Direction::Left => first_knot.x -= 1,
-        Direction::Right => first_knot.x += 1,
-    }
+    *first_knot += match direction {
+        Direction::Up => MapPos::UP,
+        Direction::Down => MapPos::DOWN,
+        Direction::Left => MapPos::LEFT,
+        Direction::Right => MapPos::RIGHT,
+    };
 
     for i in 0..(rope.knots.len() - 1) {
-        let head = rope.knots.get(i).cloned().unwrap();
-        let tail = rope.knots.get_mut(i + 1).unwrap();
-        let distance = head.distance_from(tail);
-        if distance > SQRT_2 {
-            let dist_x = (head.x - tail.x) as f32;
-            let dist_y = (head.y - tail.y) as f32;
-
-            tail.x += round_to_digit(dist_x / 2.0) as i64;
-            tail.y += round_to_digit(dist_y / 2.0) as i64;
+        let head = rope.knots[i];
+        let tail = &mut rope.knots[i + 1];
+        // a knot only moves once it is no longer touching the one in front, and then takes a
+        // single (possibly diagonal) step towards it
+        if head.chebyshev(*tail) > 1 {
+            *tail += (head - *tail).signum();
         }
     }
 
     Ok(())
 }
 
-fn round_to_digit(x: f32) -> f32 {
-    if x.is_sign_positive() {
-        x.ceil()
-    } else {
-        x.floor()
-    }
-}
-
-struct Movement {
+pub struct Movement {
     direction: Direction,
     count: u64,
 }
@@ -132,31 +134,15 @@ enum Direction {
 }
 
 #[derive(Debug)]
-struct Rope {
+pub struct Rope {
     knots: Vec<MapPos>,
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-struct MapPos {
-    x: i64,
-    y: i64,
-}
-
-impl From<(i64, i64)> for MapPos {
-    fn from((x, y): (i64, i64)) -> Self {
-        Self { x, y }
-    }
-}
-
-impl MapPos {
-    fn distance_from(&self, other: &MapPos) -> f64 {
-        f64::sqrt(((self.x - other.x).pow(2) + (self.y - other.y).pow(2)) as f64)
-    }
-}
+type MapPos = Vec2<i64>;
 
 #[cfg(test)]
 mod tests {
-    use crate::unique_tail_positions;
+    use super::{parse_movements, unique_tail_positions};
 
     #[test]
     fn example_part1() {
@@ -168,7 +154,8 @@ R 4
 D 1
 L 5
 R 2";
-        assert_eq!(13, unique_tail_positions(input, 2).unwrap());
+        let movements = parse_movements(input).unwrap();
+        assert_eq!(13, unique_tail_positions(&movements, 2).unwrap());
     }
 
     #[test]
@@ -181,6 +168,7 @@ R 17
 D 10
 L 25
 U 20";
-        assert_eq!(36, unique_tail_positions(input, 10).unwrap());
+        let movements = parse_movements(input).unwrap();
+        assert_eq!(36, unique_tail_positions(&movements, 10).unwrap());
     }
 }
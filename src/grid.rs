@@ -0,0 +1,384 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use color_eyre::eyre::{eyre, Result};
+
+/// `(x, y)` position in a grid, `x` being the column and `y` the row
+pub type Pos = (usize, usize);
+/// `(dx, dy)` offset between two positions
+pub type Step = (isize, isize);
+
+/// Steps to the 4 orthogonal neighbours: up, right, down, left (with `y` growing downwards)
+pub const ORTHOGONAL: [Step; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Steps to the 4 diagonal neighbours
+pub const DIAGONAL: [Step; 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+/// Steps to all 8 surrounding neighbours
+pub const ALL_DIRECTIONS: [Step; 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// How the cells of a [`Grid`] are stored, addressed by their row-major index
+pub trait Storage<T> {
+    fn cell(&self, index: usize) -> &T;
+    fn cell_mut(&mut self, index: usize) -> &mut T;
+}
+
+/// Dense storage, every cell is stored
+impl<T> Storage<T> for Vec<T> {
+    fn cell(&self, index: usize) -> &T {
+        &self[index]
+    }
+
+    fn cell_mut(&mut self, index: usize) -> &mut T {
+        &mut self[index]
+    }
+}
+
+/// Sparse storage, only cells that have been written to are stored and all others read as `default`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sparse<T> {
+    cells: HashMap<usize, T>,
+    default: T,
+}
+
+impl<T: Clone> Storage<T> for Sparse<T> {
+    fn cell(&self, index: usize) -> &T {
+        self.cells.get(&index).unwrap_or(&self.default)
+    }
+
+    fn cell_mut(&mut self, index: usize) -> &mut T {
+        self.cells
+            .entry(index)
+            .or_insert_with(|| self.default.clone())
+    }
+}
+
+/// A bounded 2D grid of cells, stored densely by default
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T, S = Vec<T>> {
+    width: usize,
+    height: usize,
+    cells: S,
+    _cell: std::marker::PhantomData<T>,
+}
+
+/// A grid where most cells share the same value
+pub type SparseGrid<T> = Grid<T, Sparse<T>>;
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Result<Self> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return Err(eyre!(
+                "{} cells cannot be split into rows of {width}",
+                cells.len()
+            ));
+        }
+
+        Ok(Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+            _cell: std::marker::PhantomData,
+        })
+    }
+
+    /// Parses a character map with one row per line, converting each character with `cell`
+    pub fn parse(input: &str, mut cell: impl FnMut(Pos, char) -> Result<T>) -> Result<Self> {
+        let width = input
+            .lines()
+            .next()
+            .map(|l| l.chars().count())
+            .ok_or(eyre!("Grid is empty"))?;
+
+        let mut cells = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            let row_start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(cell((x, y), c)?);
+            }
+            if cells.len() - row_start != width {
+                return Err(eyre!(
+                    "Line {} has {} cells, expected {width}",
+                    y + 1,
+                    cells.len() - row_start
+                ));
+            }
+        }
+
+        Grid::from_vec(width, cells)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+            _cell: std::marker::PhantomData,
+        }
+    }
+
+    /// Adds rows of `value` until the grid is `height` rows tall, never removes rows
+    pub fn grow_to_height(&mut self, height: usize, value: T) {
+        if height > self.height {
+            self.cells.resize(height * self.width, value);
+            self.height = height;
+        }
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    pub fn sparse(width: usize, height: usize, default: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: Sparse {
+                cells: HashMap::new(),
+                default,
+            },
+            _cell: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, S: Storage<T>> Grid<T, S> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: Pos) -> bool {
+        pos.0 < self.width && pos.1 < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.in_bounds(pos)
+            .then(|| self.cells.cell(pos.0 + pos.1 * self.width))
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            Some(self.cells.cell_mut(pos.0 + pos.1 * self.width))
+        } else {
+            None
+        }
+    }
+
+    /// `pos` moved by `step`, if that is still inside the grid
+    pub fn offset(&self, pos: Pos, step: Step) -> Option<Pos> {
+        let moved = (
+            pos.0.checked_add_signed(step.0)?,
+            pos.1.checked_add_signed(step.1)?,
+        );
+        self.in_bounds(moved).then_some(moved)
+    }
+
+    /// Orthogonal neighbours of `pos` that are inside the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |step| self.offset(pos, step))
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` that are inside the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_DIRECTIONS
+            .into_iter()
+            .filter_map(move |step| self.offset(pos, step))
+    }
+
+    /// Cells from `start` (included) repeatedly moving by `step` until leaving the grid, which
+    /// walks a row, column or diagonal from any point in either direction
+    pub fn ray(&self, start: Pos, step: Step) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        std::iter::successors(self.in_bounds(start).then_some(start), move |pos| {
+            self.offset(*pos, step)
+        })
+        .map(|pos| (pos, &self[pos]))
+    }
+
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        (0..self.width).map(move |x| &self[(x, y)])
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn rows(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T> + '_> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All positions in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their position, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().map(|pos| (pos, &self[pos]))
+    }
+
+    /// Draws the grid one row per line, top row first
+    pub fn render(&self, mut cell: impl FnMut(Pos, &T) -> char) -> String {
+        (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| cell((x, y), &self[(x, y)]))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T, S: Storage<T>> Index<Pos> for Grid<T, S> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "{pos:?} is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T, S: Storage<T>> IndexMut<Pos> for Grid<T, S> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the {width}x{height} grid"))
+    }
+}
+
+impl<T: Display, S: Storage<T>> Display for Grid<T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::{Grid, SparseGrid};
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |_, c| {
+            c.to_digit(10)
+                .ok_or(color_eyre::eyre::eyre!("Not a digit: {c}"))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = digits("123\n456");
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(6, grid[(2, 1)]);
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!("123\n456", grid.to_string());
+
+        assert!(Grid::parse("12\n3", |_, c| Ok(c)).is_err());
+        assert!(Grid::parse("", |_, c| Ok(c)).is_err());
+        assert!(Grid::parse("1x", |_, c| c
+            .to_digit(10)
+            .ok_or(color_eyre::eyre::eyre!("Not a digit")))
+        .is_err());
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = digits("123\n456\n789");
+        assert_eq!(
+            vec![(1, 0), (2, 1), (1, 2), (0, 1)],
+            grid.neighbours4((1, 1)).collect_vec()
+        );
+        assert_eq!(vec![(1, 0), (0, 1)], grid.neighbours4((0, 0)).collect_vec());
+        assert_eq!(8, grid.neighbours8((1, 1)).count());
+        assert_eq!(3, grid.neighbours8((2, 2)).count());
+    }
+
+    #[test]
+    fn iterators_test() {
+        let grid = digits("123\n456\n789");
+        assert_eq!(vec![&4, &5, &6], grid.row(1).collect_vec());
+        assert_eq!(vec![&9, &6, &3], grid.column(2).rev().collect_vec());
+        assert_eq!(
+            vec![1, 5, 9],
+            grid.ray((0, 0), (1, 1)).map(|(_, d)| *d).collect_vec()
+        );
+        assert_eq!(
+            vec![7, 5, 3],
+            grid.ray((0, 2), (1, -1)).map(|(_, d)| *d).collect_vec()
+        );
+        assert_eq!(
+            vec![6, 15, 24],
+            grid.rows().map(|r| r.sum::<u32>()).collect_vec()
+        );
+        assert_eq!(
+            vec![12, 15, 18],
+            grid.columns().map(|c| c.sum::<u32>()).collect_vec()
+        );
+        assert_eq!(Some(((2, 2), &9)), grid.iter().last());
+    }
+
+    #[test]
+    fn sparse_test() {
+        let mut grid = SparseGrid::sparse(1000, 1000, '.');
+        grid[(500, 3)] = '#';
+        assert_eq!('#', grid[(500, 3)]);
+        assert_eq!(Some(&'.'), grid.get((10, 10)));
+        assert_eq!(None, grid.get((1000, 0)));
+
+        let mut small = SparseGrid::sparse(3, 2, '.');
+        small[(1, 1)] = '#';
+        assert_eq!(
+            "...\n.#!",
+            small.render(|pos, c| if pos == (2, 1) { '!' } else { *c })
+        );
+    }
+
+    #[test]
+    fn grow_test() {
+        let mut grid = Grid::filled(2, 1, 0);
+        grid.grow_to_height(3, 1);
+        assert_eq!(3, grid.height());
+        assert_eq!("00\n11\n11", grid.to_string());
+        grid.grow_to_height(2, 5);
+        assert_eq!(3, grid.height());
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+
+/// Directory holding the puzzle inputs, resolved at compile time so binaries work from any directory
+pub const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input");
+
+/// Where a day's puzzle input is read from
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InputSource {
+    /// `src/input/day_N.txt`
+    Default,
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// `-` reads from stdin, anything else is a path to an input file
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg {
+            None => InputSource::Default,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Where the input of `day` comes from, for error messages
+    pub fn describe(&self, day: u8) -> String {
+        match self {
+            InputSource::Default => default_input_path(day).display().to_string(),
+            InputSource::Stdin => "stdin".to_string(),
+            InputSource::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+pub fn default_input_path(day: u8) -> PathBuf {
+    Path::new(INPUT_DIR).join(format!("day_{day}.txt"))
+}
+
+pub fn load_input(day: u8, source: &InputSource) -> Result<String> {
+    match source {
+        InputSource::Default => read_input_file(&default_input_path(day))
+            .wrap_err_with(|| format!("No input available for day {day}")),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .wrap_err("Could not read input from stdin")?;
+            Ok(input)
+        }
+        InputSource::File(path) => read_input_file(path),
+    }
+}
+
+/// Opens the input for reading it incrementally, for inputs too large to load with [`load_input`]
+pub fn open_input(day: u8, source: &InputSource) -> Result<Box<dyn BufRead>> {
+    match source {
+        InputSource::Default => open_input_file(&default_input_path(day))
+            .wrap_err_with(|| format!("No input available for day {day}")),
+        InputSource::Stdin => Ok(Box::new(std::io::stdin().lock())),
+        InputSource::File(path) => open_input_file(path),
+    }
+}
+
+fn open_input_file(path: &Path) -> Result<Box<dyn BufRead>> {
+    if !path.is_file() {
+        return Err(eyre!("Input file {} does not exist", path.display()));
+    }
+
+    let file = File::open(path).wrap_err_with(|| format!("Could not open {}", path.display()))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+fn read_input_file(path: &Path) -> Result<String> {
+    if !path.is_file() {
+        return Err(eyre!("Input file {} does not exist", path.display()));
+    }
+
+    std::fs::read_to_string(path).wrap_err_with(|| format!("Could not read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::BufRead, path::PathBuf};
+
+    use super::{default_input_path, load_input, open_input, InputSource};
+
+    #[test]
+    fn from_arg_test() {
+        assert_eq!(InputSource::Default, InputSource::from_arg(None));
+        assert_eq!(InputSource::Stdin, InputSource::from_arg(Some("-")));
+        assert_eq!(
+            InputSource::File(PathBuf::from("example.txt")),
+            InputSource::from_arg(Some("example.txt"))
+        );
+        assert_eq!("stdin", InputSource::Stdin.describe(1));
+        assert!(InputSource::Default
+            .describe(1)
+            .ends_with("src/input/day_1.txt"));
+    }
+
+    #[test]
+    fn load_input_test() {
+        assert!(default_input_path(1).ends_with("src/input/day_1.txt"));
+        assert!(load_input(1, &InputSource::Default).is_ok());
+
+        let path = std::env::temp_dir().join(format!(
+            "advent_of_code_2022_{}_load_input_test.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "1000\n\n2000\n").unwrap();
+        assert_eq!(
+            "1000\n\n2000\n",
+            load_input(1, &InputSource::File(path.clone())).unwrap()
+        );
+        let mut lines = open_input(1, &InputSource::File(path.clone()))
+            .unwrap()
+            .lines();
+        assert_eq!("1000", lines.next().unwrap().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let error = load_input(1, &InputSource::File(path.clone())).unwrap_err();
+        assert!(error.to_string().contains(&path.display().to_string()));
+        let error = open_input(1, &InputSource::File(path.clone()))
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains(&path.display().to_string()));
+    }
+}
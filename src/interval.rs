@@ -0,0 +1,419 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::point::Num;
+
+/// A non-empty range of integers, including both ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Num> Interval<T> {
+    /// `None` if `start` is after `end`
+    pub fn new(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    pub fn single(value: T) -> Self {
+        Interval {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// Number of integers in the interval, `None` if that doesn't fit in a `T` like for all of
+    /// `0..=u64::MAX`
+    pub fn len(&self) -> Option<T> {
+        self.end.checked_sub(self.start)?.checked_add(T::ONE)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether all of `other` is in this interval
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Whether the intervals overlap or one starts right after the other ends
+    pub fn touches(&self, other: &Self) -> bool {
+        self.overlaps(other) || adjacent(self.end, other.start) || adjacent(other.end, self.start)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The merged interval if the two touch, as otherwise their union isn't a single interval
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    /// What is left of this interval without `other`, up to two pieces
+    pub fn difference(&self, other: &Self) -> IntervalSet<T> {
+        IntervalSet::from(*self).difference(&IntervalSet::from(*other))
+    }
+}
+
+/// Whether `next` comes right after `end`, without overflowing
+fn adjacent<T: Num>(end: T, next: T) -> bool {
+    next > end && next - end == T::ONE
+}
+
+impl<T: Num> From<Interval<T>> for RangeInclusive<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..=interval.end
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Integers covered by any number of intervals, kept as sorted intervals that don't touch
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Num> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// The disjoint intervals covered, in increasing order
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers covered, `None` if that doesn't fit in a `T`
+    pub fn len(&self) -> Option<T> {
+        self.intervals
+            .iter()
+            .try_fold(T::ZERO, |len, interval| len.checked_add(interval.len()?))
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut a, mut b) = (0, 0);
+        while let (Some(left), Some(right)) = (self.intervals.get(a), other.intervals.get(b)) {
+            intervals.extend(left.intersection(right));
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut others = other.intervals.iter().peekable();
+        for interval in &self.intervals {
+            let mut rest = Some(*interval);
+            while let (Some(left), Some(cut)) = (rest, others.peek()) {
+                if cut.end < left.start {
+                    others.next();
+                } else if cut.start > left.end {
+                    break;
+                } else {
+                    if cut.start > left.start {
+                        intervals.push(Interval {
+                            start: left.start,
+                            end: cut.start - T::ONE,
+                        });
+                    }
+                    if cut.end < left.end {
+                        rest = Some(Interval {
+                            start: cut.end + T::ONE,
+                            end: left.end,
+                        });
+                        others.next();
+                    } else {
+                        rest = None;
+                    }
+                }
+            }
+            intervals.extend(rest);
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Everything in `bounds` that isn't in the set
+    pub fn complement(&self, bounds: Interval<T>) -> Self {
+        IntervalSet::from(bounds).difference(self)
+    }
+
+    /// Sorts the intervals and merges the ones that overlap or are adjacent
+    fn normalize(&mut self) {
+        self.intervals.sort_unstable();
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if last.touches(&interval) => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+}
+
+impl<T: Num> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl<T: Num> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalize();
+        set
+    }
+}
+
+impl<T: Display> Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{interval}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Intervals with a value attached to each, for finding the ones overlapping a value or interval
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T, V> {
+    /// Sorted by interval, as an implicit balanced tree where the middle of every slice is the
+    /// root of the subtree made of that slice
+    entries: Vec<(Interval<T>, V)>,
+    /// Highest end in the subtree rooted at each entry
+    max_end: Vec<T>,
+}
+
+impl<T: Num, V> IntervalTree<T, V> {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries, in order of their intervals
+    pub fn iter(&self) -> impl Iterator<Item = (Interval<T>, &V)> {
+        self.entries
+            .iter()
+            .map(|(interval, value)| (*interval, value))
+    }
+
+    /// Entries whose interval contains `value`, in order of their intervals
+    pub fn containing(&self, value: T) -> Vec<(Interval<T>, &V)> {
+        self.overlapping(Interval::single(value))
+    }
+
+    /// Entries whose interval overlaps `query`, in order of their intervals
+    pub fn overlapping(&self, query: Interval<T>) -> Vec<(Interval<T>, &V)> {
+        let mut found = Vec::new();
+        self.search(0, self.entries.len(), query, &mut found);
+        found
+    }
+
+    fn search<'a>(
+        &'a self,
+        low: usize,
+        high: usize,
+        query: Interval<T>,
+        found: &mut Vec<(Interval<T>, &'a V)>,
+    ) {
+        if low >= high {
+            return;
+        }
+        let middle = (low + high) / 2;
+        if self.max_end[middle] < query.start {
+            return;
+        }
+        self.search(low, middle, query, found);
+        let (interval, value) = &self.entries[middle];
+        // everything to the right starts after this one
+        if interval.start > query.end {
+            return;
+        }
+        if interval.overlaps(&query) {
+            found.push((*interval, value));
+        }
+        self.search(middle + 1, high, query, found);
+    }
+
+    /// Fills in `max_end` for the subtree of `low..high`, returning its highest end
+    fn index(&mut self, low: usize, high: usize) -> Option<T> {
+        if low >= high {
+            return None;
+        }
+        let middle = (low + high) / 2;
+        let max_end = [self.index(low, middle), self.index(middle + 1, high)]
+            .into_iter()
+            .flatten()
+            .fold(self.entries[middle].0.end, T::max);
+        self.max_end[middle] = max_end;
+        Some(max_end)
+    }
+}
+
+impl<T: Num, V> FromIterator<(Interval<T>, V)> for IntervalTree<T, V> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, V)>>(iter: I) -> Self {
+        let mut entries = iter.into_iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(interval, _)| *interval);
+        let mut tree = IntervalTree {
+            max_end: entries.iter().map(|(interval, _)| interval.end).collect(),
+            entries,
+        };
+        tree.index(0, tree.entries.len());
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interval, IntervalSet, IntervalTree};
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|(start, end)| Interval::new(*start, *end).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn interval_test() {
+        let a = Interval::new(2u64, 6).unwrap();
+        let b = Interval::new(4, 8).unwrap();
+        assert!(Interval::new(3, 2).is_none());
+        assert_eq!(Some(5), a.len());
+        assert!(a.overlaps(&b) && !a.contains_interval(&b));
+        assert!(a.contains_interval(&Interval::new(3, 6).unwrap()));
+        assert_eq!(Interval::new(4, 6), a.intersection(&b));
+        assert_eq!(Interval::new(2, 8), a.union(&b));
+        assert_eq!(
+            Interval::new(2, 10),
+            a.union(&Interval::new(7, 10).unwrap()),
+            "adjacent intervals merge"
+        );
+        assert_eq!(None, a.union(&Interval::new(8, 10).unwrap()));
+        assert_eq!("2-3", a.difference(&b).to_string());
+        assert_eq!(
+            "2-2, 5-6",
+            a.difference(&Interval::new(3, 4).unwrap()).to_string()
+        );
+        assert_eq!(Some(1), Interval::single(0u8).len());
+        // one more integer than the type can count
+        assert_eq!(None, Interval::new(0, u64::MAX).unwrap().len());
+        assert_eq!(Some(u64::MAX), Interval::new(1, u64::MAX).unwrap().len());
+        assert_eq!(None, Interval::new(i8::MIN, 0).unwrap().len());
+        assert_eq!(Some(i8::MAX), Interval::new(i8::MIN, -2).unwrap().len());
+    }
+
+    #[test]
+    fn merge_test() {
+        let merged = set(&[(5, 7), (-3, 0), (1, 2), (10, 12), (11, 11), (6, 8)]);
+        assert_eq!("-3-2, 5-8, 10-12", merged.to_string());
+        assert_eq!(Some(6 + 4 + 3), merged.len());
+        assert!(merged.contains(-3) && merged.contains(8) && !merged.contains(9));
+
+        let mut inserted = merged.clone();
+        inserted.insert(Interval::single(9));
+        assert_eq!("-3-2, 5-12", inserted.to_string());
+        assert!(IntervalSet::<i32>::new().is_empty());
+    }
+
+    #[test]
+    fn set_operations_test() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 11), (14, 20)]);
+        assert_eq!("0-20", a.union(&b).to_string());
+        assert_eq!("3-5, 10-11, 14-15", a.intersection(&b).to_string());
+        assert_eq!("0-2, 12-13", a.difference(&b).to_string());
+        assert_eq!("6-9, 16-20", b.difference(&a).to_string());
+        assert_eq!(
+            "-5--1, 6-9, 16-17",
+            a.complement(Interval::new(-5, 17).unwrap()).to_string()
+        );
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a, a.difference(&IntervalSet::new()));
+    }
+
+    #[test]
+    fn tree_test() {
+        let tree = [(5, 9), (1, 3), (2, 2), (8, 20), (12, 14), (-4, 0)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (start, end))| (Interval::new(start, end).unwrap(), i))
+            .collect::<IntervalTree<_, _>>();
+        let values = |found: Vec<(Interval<i32>, &usize)>| {
+            found.into_iter().map(|(_, v)| *v).collect::<Vec<_>>()
+        };
+        assert_eq!(6, tree.len());
+        assert_eq!(vec![1, 2], values(tree.containing(2)));
+        assert_eq!(vec![3, 4], values(tree.containing(13)));
+        assert!(tree.containing(4).is_empty());
+        assert_eq!(
+            vec![5, 1, 2, 0, 3],
+            values(tree.overlapping(Interval::new(0, 8).unwrap()))
+        );
+        assert!(tree.overlapping(Interval::new(21, 30).unwrap()).is_empty());
+        assert!(IntervalTree::<i32, ()>::from_iter([])
+            .containing(0)
+            .is_empty());
+    }
+}
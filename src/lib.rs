@@ -0,0 +1,100 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+
+use crate::{
+    input::{load_input, InputSource},
+    solution::{DynSolution, ParsedInput},
+};
+
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_2;
+pub mod day_20;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod parse;
+pub mod point;
+pub mod scaffold;
+pub mod solution;
+
+pub const DAYS: &[&dyn DynSolution] = &[
+    &day_1::Day1,
+    &day_2::Day2,
+    &day_3::Day3,
+    &day_4::Day4,
+    &day_5::Day5,
+    &day_6::Day6,
+    &day_7::Day7,
+    &day_8::Day8,
+    &day_9::Day9,
+    &day_10::Day10,
+    &day_11::Day11,
+    &day_12::Day12,
+    &day_13::Day13,
+    &day_14::Day14,
+    &day_15::Day15,
+    &day_16::Day16,
+    &day_17::Day17,
+    &day_18::Day18,
+    &day_19::Day19,
+    &day_20::Day20,
+];
+
+pub fn find_day(number: u8) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().find(|d| d.day() == number).copied()
+}
+
+/// Reads and parses the input of `day`, naming the input in parse errors
+pub fn load_and_parse(day: &dyn DynSolution, source: &InputSource) -> Result<ParsedInput> {
+    let input = load_input(day.day(), source)?;
+    day.parse(&input)
+        .wrap_err_with(|| format!("Could not parse {}", source.describe(day.day())))
+}
+
+/// What the individual `day_N` binaries run: both parts of a single day, printed one per line.
+/// `input` is a path to the puzzle input, or `-` for stdin, defaulting to `src/input/day_N.txt`
+pub fn run_day(number: u8, input: Option<&str>) -> Result<()> {
+    let day = find_day(number).ok_or(eyre!("Day {number} not found"))?;
+    let input = load_and_parse(day, &InputSource::from_arg(input))?;
+
+    println!("Day {number}");
+    for part in [1, 2] {
+        let answer = day.part(part, &input)?;
+        if answer.contains('\n') {
+            println!("Part {part}:\n{answer}");
+        } else {
+            println!("Part {part}: {answer}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DAYS;
+
+    #[test]
+    fn registry_test() {
+        let numbers = DAYS.iter().map(|d| d.day()).collect::<Vec<_>>();
+        assert_eq!((1..=DAYS.len() as u8).collect::<Vec<_>>(), numbers);
+    }
+}
@@ -1,3 +1,503 @@
-fn main() {
-    println!("Run with --bin for each individual day");
+use std::{ops::RangeInclusive, path::Path, time::Instant};
+
+use advent_of_code_2022::{
+    answers::{default_answers_path, Answers, Verdict},
+    bench::{bench_day, DayBenchmark},
+    client::{default_config_path, Client, Config, SubmitOutcome},
+    input::{default_input_path, load_input, InputSource},
+    load_and_parse,
+    scaffold::{new_day, CRATE_DIR},
+    solution::DynSolution,
+    DAYS,
+};
+use color_eyre::eyre::{eyre, Result};
+use itertools::Itertools;
+
+const USAGE: &str = "Usage: advent_of_code_2022 [run] [DAYS] [--part <1|2>] [--input <PATH>]
+       advent_of_code_2022 bench [DAYS] [--part <1|2>] [--input <PATH>] [--runs <N>] [--json]
+       advent_of_code_2022 verify [DAYS] [--part <1|2>]
+       advent_of_code_2022 fetch DAY [--force]
+       advent_of_code_2022 submit DAY --part <1|2> [--answer <ANSWER>] [--input <PATH>]
+       advent_of_code_2022 new-day DAY
+
+DAYS is a single day (`5`), an inclusive range of days (`3..7` or `3-7`) or `all` (default).
+--input reads the puzzle input from PATH (or stdin for `-`) instead of `src/input/day_N.txt`,
+and can only be used with a single day.
+
+`bench` times parsing and each part separately over N runs (default 5) and reports the
+min/median/max of each, or prints them as JSON with --json.
+
+`verify` checks the answers for the real inputs against `src/input/answers.toml` and fails if
+any of them changed. Run it with `--release`, some days are slow in debug builds.
+
+`fetch` downloads the input of DAY to `src/input/day_N.txt`, unless it is already there (or
+--force is given). `submit` posts the answer to a part of DAY (computed from its input unless
+--answer is given) and records whether it was accepted in `src/input/answers.toml`.
+Both need the session cookie of a logged in account in the AOC_SESSION environment variable
+or as `session` in `aoc.toml`. The server can be changed with AOC_BASE_URL or `base_url`.
+
+`new-day` generates `src/day_N.rs` from a template along with its `day_N` binary and empty
+input and example files, and registers it in `src/lib.rs` and `Cargo.toml`.
+
+Individual days can still be run with `cargo run --bin day_N [PATH]`.";
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let args = std::env::args().skip(1).collect_vec();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let (command, selection) = parse_args(&args).map_err(|e| eyre!("{e}\n\n{USAGE}"))?;
+    if let Command::NewDay = command {
+        // the day does not exist yet, so it can't be selected from the registry
+        return scaffold(*selection.days.start());
+    }
+
+    let days = DAYS
+        .iter()
+        .filter(|day| selection.days.contains(&day.day()))
+        .copied()
+        .collect_vec();
+    if days.is_empty() {
+        return Err(eyre!("No days matched the selection"));
+    }
+
+    match command {
+        Command::Run => run(&days, &selection),
+        Command::Bench { runs, json } => bench(&days, &selection, runs, json),
+        Command::Verify => verify(&days, &selection),
+        Command::Fetch { force } => fetch(days[0], force),
+        Command::Submit { answer } => submit(days[0], &selection, answer),
+        Command::NewDay => unreachable!("handled before selecting days"),
+    }
+}
+
+fn run(days: &[&dyn DynSolution], selection: &Selection) -> Result<()> {
+    let mut results = Vec::new();
+    for day in days {
+        let start = Instant::now();
+        let parsed = load_and_parse(*day, &selection.input);
+        let parse_ms = elapsed_ms(start);
+
+        for (i, part) in selection.parts().into_iter().enumerate() {
+            let start = Instant::now();
+            let answer = match &parsed {
+                Ok(input) => day.part(part, input),
+                Err(e) => Err(eyre!("{e:#}")),
+            };
+            results.push(RunResult {
+                day: day.day(),
+                part,
+                answer: answer.unwrap_or_else(|e| format!("ERROR: {e:#}")),
+                // parsing is shared between parts, so it is only reported once per day
+                parse_ms: (i == 0).then_some(parse_ms),
+                solve_ms: elapsed_ms(start),
+            });
+        }
+    }
+
+    print_table(&results);
+    Ok(())
+}
+
+fn bench(days: &[&dyn DynSolution], selection: &Selection, runs: usize, json: bool) -> Result<()> {
+    let mut benchmarks = Vec::new();
+    for day in days {
+        if !json {
+            eprintln!("Benchmarking day {} ({runs} runs)...", day.day());
+        }
+        let benchmark = match load_input(day.day(), &selection.input) {
+            Ok(input) => bench_day(*day, &input, &selection.parts(), runs),
+            Err(e) => DayBenchmark {
+                day: day.day(),
+                runs,
+                phases: Vec::new(),
+                error: Some(format!("{e:#}")),
+            },
+        };
+        benchmarks.push(benchmark);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&benchmarks)?);
+    } else {
+        print_bench_table(&benchmarks);
+    }
+    Ok(())
+}
+
+fn verify(days: &[&dyn DynSolution], selection: &Selection) -> Result<()> {
+    let answers = Answers::load(&default_answers_path())?;
+
+    let mut failures = 0;
+    let mut missing = 0;
+    println!("Day | Part | Result  | Details");
+    println!("----+------+---------+--------");
+    for day in days {
+        let parsed = load_and_parse(*day, &selection.input);
+
+        for part in selection.parts() {
+            let answer = match &parsed {
+                Ok(input) => day.part(part, input),
+                Err(e) => Err(eyre!("{e:#}")),
+            };
+            let (status, details) = match (answer, answers.expected(day.day(), part)) {
+                (Ok(answer), _) => match answers.check(day.day(), part, &answer) {
+                    Verdict::Pass => ("pass", String::new()),
+                    Verdict::Fail { expected } => {
+                        failures += 1;
+                        (
+                            "FAIL",
+                            format!("expected {:?}, got {:?}", expected.trim_end(), answer),
+                        )
+                    }
+                    Verdict::Missing => {
+                        missing += 1;
+                        ("missing", format!("got {answer:?}"))
+                    }
+                },
+                (Err(e), None) => {
+                    missing += 1;
+                    ("missing", format!("ERROR: {e:#}"))
+                }
+                (Err(e), Some(_)) => {
+                    failures += 1;
+                    ("FAIL", format!("ERROR: {e:#}"))
+                }
+            };
+            println!("{:>3} | {part:>4} | {status:<7} | {details}", day.day());
+        }
+    }
+
+    println!();
+    println!("{failures} failed, {missing} without a recorded answer");
+    if failures > 0 {
+        return Err(eyre!("{failures} answers did not match"));
+    }
+    Ok(())
+}
+
+fn fetch(day: &dyn DynSolution, force: bool) -> Result<()> {
+    let path = default_input_path(day.day());
+    if path.is_file() && !force {
+        println!(
+            "Input of day {} is already in {}, use --force to download it again",
+            day.day(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let client = Client::from_env(Config::load(&default_config_path())?)?;
+    let input = client.fetch_input(day.day())?;
+    std::fs::write(&path, input).map_err(|e| eyre!("Could not write {}: {e}", path.display()))?;
+    println!("Saved the input of day {} to {}", day.day(), path.display());
+    Ok(())
+}
+
+fn submit(day: &dyn DynSolution, selection: &Selection, answer: Option<String>) -> Result<()> {
+    let part = selection
+        .part
+        .ok_or(eyre!("`submit` needs the part to submit with --part"))?;
+    let answer = match answer {
+        Some(answer) => answer,
+        None => day.part(part, &load_and_parse(day, &selection.input)?)?,
+    };
+
+    let answers_path = default_answers_path();
+    let mut answers = Answers::load(&answers_path)?;
+    match answers.check(day.day(), part, &answer) {
+        Verdict::Pass => {
+            println!(
+                "{answer} is already recorded as the answer to day {} part {part}",
+                day.day()
+            );
+            return Ok(());
+        }
+        Verdict::Fail { expected } => {
+            return Err(eyre!(
+                "Day {} part {part} was already solved with {:?}, not submitting {answer:?}",
+                day.day(),
+                expected.trim_end()
+            ))
+        }
+        Verdict::Missing if answers.is_known_wrong(day.day(), part, &answer) => {
+            return Err(eyre!(
+                "{answer:?} was already rejected, not submitting it again"
+            ))
+        }
+        Verdict::Missing => {}
+    }
+
+    let client = Client::from_env(Config::load(&default_config_path())?)?;
+    match client.submit(day.day(), part, &answer)? {
+        SubmitOutcome::Correct => {
+            answers.record_correct(day.day(), part, &answer);
+            answers.save(&answers_path)?;
+            println!(
+                "{answer} is correct, recorded in {}",
+                answers_path.display()
+            );
+        }
+        SubmitOutcome::Incorrect(message) => {
+            answers.record_wrong(day.day(), part, &answer);
+            answers.save(&answers_path)?;
+            return Err(eyre!("{answer} was rejected: {message}"));
+        }
+        SubmitOutcome::TooSoon(message) => return Err(eyre!("{message}")),
+        SubmitOutcome::AlreadySolved => {
+            return Err(eyre!(
+                "Day {} part {part} is already solved, but its answer is not recorded in {}",
+                day.day(),
+                answers_path.display()
+            ))
+        }
+        SubmitOutcome::Unknown(message) => {
+            return Err(eyre!("Unexpected response to the submission: {message}"))
+        }
+    }
+    Ok(())
+}
+
+fn scaffold(day: u8) -> Result<()> {
+    for path in new_day(Path::new(CRATE_DIR), day)? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+enum Command {
+    Run,
+    Bench { runs: usize, json: bool },
+    Verify,
+    Fetch { force: bool },
+    Submit { answer: Option<String> },
+    NewDay,
+}
+
+struct Selection {
+    days: RangeInclusive<u8>,
+    part: Option<u8>,
+    input: InputSource,
+}
+
+impl Selection {
+    fn parts(&self) -> Vec<u8> {
+        [1, 2]
+            .into_iter()
+            .filter(|part| self.part.is_none_or(|p| p == *part))
+            .collect()
+    }
+}
+
+struct RunResult {
+    day: u8,
+    part: u8,
+    answer: String,
+    parse_ms: Option<f64>,
+    solve_ms: f64,
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+fn parse_args(args: &[String]) -> Result<(Command, Selection)> {
+    let mut command = Command::Run;
+    let mut selection = Selection {
+        days: 1..=u8::MAX,
+        part: None,
+        input: InputSource::Default,
+    };
+
+    let mut args = args.iter().peekable();
+    match args.peek().map(|a| a.as_str()) {
+        Some("run") => {
+            args.next();
+        }
+        Some("bench") => {
+            args.next();
+            command = Command::Bench {
+                runs: 5,
+                json: false,
+            };
+        }
+        Some("verify") => {
+            args.next();
+            command = Command::Verify;
+        }
+        Some("fetch") => {
+            args.next();
+            command = Command::Fetch { force: false };
+        }
+        Some("submit") => {
+            args.next();
+            command = Command::Submit { answer: None };
+        }
+        Some("new-day") => {
+            args.next();
+            command = Command::NewDay;
+        }
+        _ => {}
+    }
+
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), &mut command) {
+            ("-r" | "--runs", Command::Bench { runs, .. }) => {
+                *runs = args
+                    .next()
+                    .ok_or(eyre!("--runs requires a value"))?
+                    .parse()?;
+                if *runs == 0 {
+                    return Err(eyre!("--runs must be at least 1"));
+                }
+            }
+            ("--json", Command::Bench { json, .. }) => *json = true,
+            ("-r" | "--runs" | "--json", _) => {
+                return Err(eyre!("{arg} can only be used with `bench`"))
+            }
+            ("-f" | "--force", Command::Fetch { force }) => *force = true,
+            ("-f" | "--force", _) => return Err(eyre!("{arg} can only be used with `fetch`")),
+            ("-a" | "--answer", Command::Submit { answer }) => {
+                *answer = Some(
+                    args.next()
+                        .ok_or(eyre!("--answer requires a value"))?
+                        .clone(),
+                );
+            }
+            ("-a" | "--answer", _) => return Err(eyre!("{arg} can only be used with `submit`")),
+            ("-p" | "--part", _) => {
+                let part = args
+                    .next()
+                    .ok_or(eyre!("--part requires a value"))?
+                    .parse::<u8>()?;
+                if !(1..=2).contains(&part) {
+                    return Err(eyre!("Part must be 1 or 2, got {part}"));
+                }
+                selection.part = Some(part);
+            }
+            ("-i" | "--input", _) => {
+                let path = args.next().ok_or(eyre!("--input requires a path"))?;
+                selection.input = InputSource::from_arg(Some(path));
+            }
+            (days, _) => selection.days = parse_days(days)?,
+        }
+    }
+
+    if selection.input != InputSource::Default && selection.days.start() != selection.days.end() {
+        return Err(eyre!("--input can only be used when running a single day"));
+    }
+    if matches!(
+        command,
+        Command::Fetch { .. } | Command::Submit { .. } | Command::NewDay
+    ) && selection.days.start() != selection.days.end()
+    {
+        return Err(eyre!("`fetch`, `submit` and `new-day` need a single day"));
+    }
+
+    Ok((command, selection))
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<u8>> {
+    if s == "all" {
+        return Ok(1..=u8::MAX);
+    }
+
+    match s.split_once("..").or_else(|| s.split_once('-')) {
+        Some((start, end)) => Ok(start.parse()?..=end.trim_start_matches('=').parse()?),
+        None => {
+            let day = s
+                .parse::<u8>()
+                .map_err(|_| eyre!("Unrecognized argument '{s}'"))?;
+            Ok(day..=day)
+        }
+    }
+}
+
+fn print_table(results: &[RunResult]) {
+    let answer_width = results
+        .iter()
+        .flat_map(|r| r.answer.lines())
+        .map(|l| l.chars().count())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or(0);
+    let separator = format!(
+        "----+------+-{}-+-----------+-----------",
+        "-".repeat(answer_width)
+    );
+
+    println!(
+        "Day | Part | {:<answer_width$} | {:>9} | {:>9}",
+        "Answer", "Parse", "Solve"
+    );
+    println!("{separator}");
+    for result in results {
+        let mut lines = result.answer.lines();
+        println!(
+            "{:>3} | {:>4} | {:<answer_width$} | {:>9} | {:>7.2}ms",
+            result.day,
+            result.part,
+            lines.next().unwrap_or(""),
+            result
+                .parse_ms
+                .map(|ms| format!("{ms:.2}ms"))
+                .unwrap_or_default(),
+            result.solve_ms
+        );
+        // multi-line answers (like the day 10 screen) continue in the answer column
+        for line in lines {
+            println!("    |      | {line:<answer_width$} |           |");
+        }
+    }
+
+    let total_ms: f64 = results
+        .iter()
+        .map(|r| r.parse_ms.unwrap_or(0.0) + r.solve_ms)
+        .sum();
+    println!("{separator}");
+    println!(
+        "{:>10}   {:>answer_width$}   {total_ms:>21.2}ms",
+        "", "Total"
+    );
+}
+
+fn print_bench_table(benchmarks: &[DayBenchmark]) {
+    println!(
+        "Day | Phase  | {:>10} | {:>10} | {:>10}",
+        "Min", "Median", "Max"
+    );
+    println!("----+--------+------------+------------+-----------");
+    for benchmark in benchmarks {
+        for phase in &benchmark.phases {
+            println!(
+                "{:>3} | {:<6} | {:>8.3}ms | {:>8.3}ms | {:>8.3}ms",
+                benchmark.day,
+                phase.phase.to_string(),
+                phase.stats.min_ms,
+                phase.stats.median_ms,
+                phase.stats.max_ms
+            );
+        }
+        if let Some(error) = &benchmark.error {
+            println!("{:>3} | ERROR: {error}", benchmark.day);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_days;
+
+    #[test]
+    fn parse_days_test() {
+        assert_eq!(5..=5, parse_days("5").unwrap());
+        assert_eq!(3..=7, parse_days("3..7").unwrap());
+        assert_eq!(3..=7, parse_days("3..=7").unwrap());
+        assert_eq!(3..=7, parse_days("3-7").unwrap());
+        assert_eq!(1..=u8::MAX, parse_days("all").unwrap());
+        assert!(parse_days("five").is_err());
+    }
 }
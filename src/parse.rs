@@ -0,0 +1,149 @@
+use std::fmt::Display;
+
+use nom::{
+    error::{VerboseError, VerboseErrorKind},
+    IResult,
+};
+
+/// Result of the nom parsers, keeping every error along the way for [`ParseError`]
+pub type ParseResult<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// A parse failure pointing at where in the input it happened
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    source_line: String,
+    message: String,
+}
+
+impl ParseError {
+    /// `at` has to be a slice of `input`, it is where the error gets reported
+    pub fn at(input: &str, at: &str, message: impl Display) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| *offset <= input.len())
+            .unwrap_or(0);
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            source_line: input[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Reports at the innermost failure, with the contexts it was in
+    pub fn from_nom(input: &str, error: VerboseError<&str>) -> Self {
+        let Some((at, kind)) = error.errors.first() else {
+            return ParseError::at(input, input, "invalid input");
+        };
+
+        let mut message = match kind {
+            VerboseErrorKind::Context(context) => format!("expected {context}"),
+            VerboseErrorKind::Char(c) => format!("expected '{c}'"),
+            VerboseErrorKind::Nom(kind) => format!("expected {}", kind.description()),
+        };
+        for (_, kind) in error.errors.iter().skip(1) {
+            if let VerboseErrorKind::Context(context) = kind {
+                message.push_str(&format!(" in {context}"));
+            }
+        }
+
+        ParseError::at(input, at, message)
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Turns the result of running a parser on `at` (a slice of the whole `input`) into its output,
+/// failing with the location in `input` if parsing failed or anything but whitespace is left over
+pub fn finish<'a, O>(input: &'a str, result: ParseResult<'a, O>) -> Result<O, ParseError> {
+    match result {
+        Ok((remaining, _)) if !remaining.trim().is_empty() => Err(ParseError::at(
+            input,
+            remaining,
+            "unexpected trailing input",
+        )),
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::from_nom(input, e)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(
+            input,
+            &input[input.len()..],
+            "unexpected end of input",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{bytes::complete::tag, character::complete::u64, error::context, sequence::preceded};
+
+    use super::{finish, ParseError, ParseResult};
+
+    fn number(s: &str) -> ParseResult<'_, u64> {
+        context("number", preceded(tag("n="), u64))(s)
+    }
+
+    #[test]
+    fn location_test() {
+        let input = "n=1\nn=x\n";
+        let line = input.lines().nth(1).unwrap();
+        let error = finish(input, number(line)).unwrap_err();
+        assert_eq!((2, 3), (error.line(), error.column()));
+        assert_eq!(
+            "line 2, column 3: expected Digit in number\n  |\n2 | n=x\n  |   ^",
+            error.to_string()
+        );
+
+        assert_eq!(
+            1,
+            finish(input, number(input.lines().next().unwrap())).unwrap()
+        );
+    }
+
+    #[test]
+    fn trailing_input_test() {
+        let input = "n=12 and more";
+        let error = finish(input, number(input)).unwrap_err();
+        assert_eq!(
+            ParseError {
+                line: 1,
+                column: 5,
+                source_line: input.to_string(),
+                message: "unexpected trailing input".to_string()
+            },
+            error
+        );
+        assert_eq!(12, finish("n=12\n", number("n=12\n")).unwrap());
+    }
+}
@@ -0,0 +1,351 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// Integer types usable as coordinates
+pub trait Num:
+    Copy + Ord + Hash + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `None` on overflow
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// `None` on overflow
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+/// Coordinates that can point in any direction
+pub trait Signed: Num + Neg<Output = Self> {
+    const NEG_ONE: Self;
+
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            std::cmp::Ordering::Less => Self::NEG_ONE,
+            std::cmp::Ordering::Equal => Self::ZERO,
+            std::cmp::Ordering::Greater => Self::ONE,
+        }
+    }
+}
+
+macro_rules! impl_num {
+    ($($t:ty),*) => {
+        $(impl Num for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+        })*
+    };
+}
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(impl Signed for $t {
+            const NEG_ONE: Self = -1;
+        })*
+    };
+}
+impl_num!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Num> Vec2<T> {
+    pub const ZERO: Self = Vec2::new(T::ZERO, T::ZERO);
+
+    pub const fn new(x: T, y: T) -> Self {
+        Vec2 { x, y }
+    }
+
+    /// Number of orthogonal steps between the two points
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Number of steps between the two points when diagonal steps are allowed
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+/// Directions are given with `y` pointing up
+impl<T: Signed> Vec2<T> {
+    pub const UP: Self = Vec2::new(T::ZERO, T::ONE);
+    pub const DOWN: Self = Vec2::new(T::ZERO, T::NEG_ONE);
+    pub const LEFT: Self = Vec2::new(T::NEG_ONE, T::ZERO);
+    pub const RIGHT: Self = Vec2::new(T::ONE, T::ZERO);
+    pub const ORTHOGONAL: [Self; 4] = [Self::UP, Self::RIGHT, Self::DOWN, Self::LEFT];
+    pub const ALL_DIRECTIONS: [Self; 8] = [
+        Self::UP,
+        Vec2::new(T::ONE, T::ONE),
+        Self::RIGHT,
+        Vec2::new(T::ONE, T::NEG_ONE),
+        Self::DOWN,
+        Vec2::new(T::NEG_ONE, T::NEG_ONE),
+        Self::LEFT,
+        Vec2::new(T::NEG_ONE, T::ONE),
+    ];
+
+    /// Each component reduced to -1, 0 or 1, i.e. a single (possibly diagonal) step towards it
+    pub fn signum(self) -> Self {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Quarter turn counter-clockwise around the origin
+    pub fn rotate_left(self) -> Self {
+        Vec2::new(-self.y, self.x)
+    }
+
+    /// Quarter turn clockwise around the origin
+    pub fn rotate_right(self) -> Self {
+        Vec2::new(self.y, -self.x)
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Self::ORTHOGONAL.into_iter().map(move |d| self + d)
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Self::ALL_DIRECTIONS.into_iter().map(move |d| self + d)
+    }
+}
+
+impl<T: Num> Vec3<T> {
+    pub const ZERO: Self = Vec3::new(T::ZERO, T::ZERO, T::ZERO);
+
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    /// Number of orthogonal steps between the two points
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// Number of steps between the two points when diagonal steps are allowed
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+}
+
+impl<T: Signed> Vec3<T> {
+    /// Unit steps along each axis, in both directions
+    pub const ORTHOGONAL: [Self; 6] = [
+        Vec3::new(T::ONE, T::ZERO, T::ZERO),
+        Vec3::new(T::NEG_ONE, T::ZERO, T::ZERO),
+        Vec3::new(T::ZERO, T::ONE, T::ZERO),
+        Vec3::new(T::ZERO, T::NEG_ONE, T::ZERO),
+        Vec3::new(T::ZERO, T::ZERO, T::ONE),
+        Vec3::new(T::ZERO, T::ZERO, T::NEG_ONE),
+    ];
+
+    /// Each component reduced to -1, 0 or 1, i.e. a single (possibly diagonal) step towards it
+    pub fn signum(self) -> Self {
+        Vec3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// Quarter turn counter-clockwise around the z axis, looking down from positive z
+    pub fn rotate_z(self) -> Self {
+        Vec3::new(-self.y, self.x, self.z)
+    }
+
+    /// Quarter turn counter-clockwise around the x axis, looking down from positive x
+    pub fn rotate_x(self) -> Self {
+        Vec3::new(self.x, -self.z, self.y)
+    }
+
+    /// Quarter turn counter-clockwise around the y axis, looking down from positive y
+    pub fn rotate_y(self) -> Self {
+        Vec3::new(self.z, self.y, -self.x)
+    }
+
+    /// The 6 points sharing a face with this one
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        Self::ORTHOGONAL.into_iter().map(move |d| self + d)
+    }
+
+    /// The 26 points sharing a face, edge or corner with this one
+    pub fn neighbours26(self) -> impl Iterator<Item = Self> {
+        let steps = [T::NEG_ONE, T::ZERO, T::ONE];
+        steps
+            .into_iter()
+            .flat_map(move |x| steps.into_iter().map(move |y| (x, y)))
+            .flat_map(move |(x, y)| steps.into_iter().map(move |z| Vec3::new(x, y, z)))
+            .filter(|d| *d != Self::ZERO)
+            .map(move |d| self + d)
+    }
+}
+
+macro_rules! impl_ops {
+    ($name:ident { $($field:ident),* }) => {
+        impl<T: Num> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $name { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl<T: Num> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $name { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        /// Scaling by a factor
+        impl<T: Num> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                $name { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl<T: Signed> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $name { $($field: -self.$field),* }
+            }
+        }
+
+        impl<T: Num> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Num> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+impl_ops!(Vec2 { x, y });
+impl_ops!(Vec3 { x, y, z });
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(v: Vec2<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Vec3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::{Vec2, Vec3};
+
+    #[test]
+    fn arithmetic_test() {
+        let a = Vec2::new(3, -2);
+        let b = Vec2::new(-1, 5);
+        assert_eq!(Vec2::new(2, 3), a + b);
+        assert_eq!(Vec2::new(4, -7), a - b);
+        assert_eq!(Vec2::new(6, -4), a * 2);
+        assert_eq!(Vec2::new(-3, 2), -a);
+        assert_eq!(Vec2::new(1, -1), a.signum());
+        assert_eq!(11, a.manhattan(b));
+        assert_eq!(7, a.chebyshev(b));
+
+        let mut c = Vec2::new(1usize, 1);
+        c += Vec2::new(2, 0);
+        assert_eq!(Vec2::new(3, 1), c);
+        assert_eq!(2, c.manhattan(Vec2::new(1, 1)));
+        assert_eq!((3, 1), c.into());
+
+        let d = Vec3::new(1, 2, 3);
+        assert_eq!(Vec3::new(2, 4, 6), d + d);
+        assert_eq!(Vec3::ZERO, d - d);
+        assert_eq!(6, d.manhattan(Vec3::ZERO));
+        assert_eq!(3, d.chebyshev(Vec3::ZERO));
+        assert_eq!("(1, 2, 3)", d.to_string());
+    }
+
+    #[test]
+    fn rotation_test() {
+        assert_eq!(Vec2::<i32>::UP, Vec2::RIGHT.rotate_left());
+        assert_eq!(Vec2::<i32>::DOWN, Vec2::RIGHT.rotate_right());
+        assert_eq!(
+            Vec2::new(2, 1),
+            Vec2::new(2, 1)
+                .rotate_left()
+                .rotate_left()
+                .rotate_right()
+                .rotate_right()
+        );
+        assert_eq!(Vec3::new(0, 1, 0), Vec3::new(1, 0, 0).rotate_z());
+        assert_eq!(Vec3::new(0, 0, 1), Vec3::new(0, 1, 0).rotate_x());
+        assert_eq!(Vec3::new(1, 0, 0), Vec3::new(0, 0, 1).rotate_y());
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let origin = Vec2::new(0i32, 0);
+        assert_eq!(4, origin.neighbours4().unique().count());
+        assert!(origin.neighbours8().all(|n| n.chebyshev(origin) == 1));
+        assert_eq!(8, origin.neighbours8().unique().count());
+
+        let origin = Vec3::new(0i64, 0, 0);
+        assert!(origin.neighbours6().all(|n| n.manhattan(origin) == 1));
+        assert_eq!(26, origin.neighbours26().unique().count());
+    }
+}
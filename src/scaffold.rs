@@ -0,0 +1,269 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+
+const DAY_TEMPLATE: &str = include_str!("scaffold/day.rs.tmpl");
+const BIN_TEMPLATE: &str = include_str!("scaffold/bin.rs.tmpl");
+
+/// Root of the crate the scaffolding is generated into
+pub const CRATE_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+/// Generates the module, binary and (empty) input files of `day` under `root` and registers
+/// the day in `src/lib.rs` and `Cargo.toml`, returning the files it created or changed
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    if day == 0 {
+        return Err(eyre!("Days start at 1"));
+    }
+    let module = root.join(format!("src/day_{day}.rs"));
+    let bin = root.join(format!("src/bin/day_{day}.rs"));
+    // nothing is written unless none of the generated files would be overwritten
+    if let Some(existing) = [&module, &bin].into_iter().find(|path| path.exists()) {
+        return Err(eyre!("{} already exists", existing.display()));
+    }
+
+    let lib = root.join("src/lib.rs");
+    let manifest = root.join("Cargo.toml");
+    let lib_source = register_module(&read(&lib)?, day)?;
+    let manifest_source = register_bin(&read(&manifest)?, day)?;
+
+    let mut changed = Vec::new();
+    for (path, contents) in [
+        (module, fill(DAY_TEMPLATE, day)),
+        (bin, fill(BIN_TEMPLATE, day)),
+        (lib, lib_source),
+        (manifest, manifest_source),
+    ] {
+        write(&path, &contents)?;
+        changed.push(path);
+    }
+    // inputs may already have been fetched
+    for name in [format!("day_{day}.txt"), format!("example_day_{day}.txt")] {
+        let path = root.join("src/input").join(name);
+        if !path.exists() {
+            write(&path, "")?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+fn fill(template: &str, day: u8) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).wrap_err_with(|| format!("Could not read {}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .wrap_err_with(|| format!("Could not create {}", parent.display()))?;
+    }
+    std::fs::write(path, contents).wrap_err_with(|| format!("Could not write {}", path.display()))
+}
+
+/// Day number of a `day_N` name at the start of `s`
+fn day_of(s: &str) -> Option<u8> {
+    let digits = s.strip_prefix("day_")?;
+    let end = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    digits[..end].parse().ok()
+}
+
+/// Adds `pub mod day_N;` (keeping the modules sorted) and the day to `DAYS` (in day order)
+fn register_module(lib: &str, day: u8) -> Result<String> {
+    let name = format!("day_{day}");
+    let mut lines = lib.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, line.strip_prefix("pub mod ")?.strip_suffix(';')?)))
+        .collect::<Vec<_>>();
+    if modules.iter().any(|(_, module)| *module == name) {
+        return Err(eyre!("{name} is already declared in src/lib.rs"));
+    }
+    let (last, _) = modules
+        .last()
+        .ok_or(eyre!("No modules declared in src/lib.rs"))?;
+    let at = modules
+        .iter()
+        .find(|(_, module)| *module > name.as_str())
+        .map_or(last + 1, |(i, _)| *i);
+    lines.insert(at, format!("pub mod {name};"));
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS"))
+        .ok_or(eyre!("No DAYS registry in src/lib.rs"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "];")
+            .ok_or(eyre!("DAYS registry in src/lib.rs is not terminated"))?;
+    let at = (start + 1..end)
+        .find(|i| day_of(lines[*i].trim().trim_start_matches('&')).is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(at, format!("    &{name}::Day{day},"));
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds a `[[bin]]` for `day_N` among the other days' binaries, in day order
+fn register_bin(manifest: &str, day: u8) -> Result<String> {
+    let name = format!("day_{day}");
+    let mut lines = manifest.lines().map(str::to_string).collect::<Vec<_>>();
+    if lines
+        .iter()
+        .any(|line| *line == format!("name = \"{name}\""))
+    {
+        return Err(eyre!("{name} is already a binary in Cargo.toml"));
+    }
+
+    // each `[[bin]]` is followed by its name and path
+    let bins = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| *line == "[[bin]]")
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let after_last = bins.last().map(|i| i + 3);
+    let at = bins
+        .iter()
+        .find(|i| {
+            lines
+                .get(*i + 1)
+                .and_then(|line| day_of(line.strip_prefix("name = \"")?))
+                .is_some_and(|d| d > day)
+        })
+        .copied()
+        .or(after_last)
+        .ok_or(eyre!("No [[bin]] targets in Cargo.toml"))?;
+
+    lines.splice(
+        at..at,
+        [
+            "[[bin]]".to_string(),
+            format!("name = \"{name}\""),
+            format!("path = \"src/bin/{name}.rs\""),
+        ],
+    );
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new_day, register_bin, register_module};
+
+    const LIB: &str = "use crate::solution::DynSolution;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod grid;
+
+pub const DAYS: &[&dyn DynSolution] = &[
+    &day_1::Day1,
+    &day_2::Day2,
+    &day_10::Day10,
+];
+";
+
+    const MANIFEST: &str = "[package]
+name = \"advent_of_code_2022\"
+
+[[bin]]
+name = \"day_1\"
+path = \"src/bin/day_1.rs\"
+[[bin]]
+name = \"day_10\"
+path = \"src/bin/day_10.rs\"
+
+[dependencies]
+";
+
+    #[test]
+    fn register_module_test() {
+        let lib = register_module(LIB, 3).unwrap();
+        assert!(lib.contains("pub mod day_2;\npub mod day_3;\npub mod grid;"));
+        assert!(lib.contains("&day_2::Day2,\n    &day_3::Day3,\n    &day_10::Day10,"));
+
+        let lib = register_module(LIB, 11).unwrap();
+        assert!(lib.contains("pub mod day_10;\npub mod day_11;\npub mod day_2;"));
+        assert!(lib.contains("&day_10::Day10,\n    &day_11::Day11,\n];"));
+
+        assert!(register_module(LIB, 2).is_err());
+    }
+
+    #[test]
+    fn register_bin_test() {
+        let manifest = register_bin(MANIFEST, 2).unwrap();
+        assert!(manifest.contains(
+            "path = \"src/bin/day_1.rs\"\n[[bin]]\nname = \"day_2\"\npath = \"src/bin/day_2.rs\"\n[[bin]]\nname = \"day_10\""
+        ));
+
+        let manifest = register_bin(MANIFEST, 11).unwrap();
+        assert!(manifest.contains(
+            "path = \"src/bin/day_10.rs\"\n[[bin]]\nname = \"day_11\"\npath = \"src/bin/day_11.rs\"\n\n[dependencies]"
+        ));
+
+        assert!(register_bin(MANIFEST, 10).is_err());
+    }
+
+    #[test]
+    fn new_day_test() {
+        let root = std::env::temp_dir().join(format!(
+            "advent_of_code_2022_{}_new_day_test",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/input")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        std::fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+        std::fs::write(root.join("src/input/day_3.txt"), "fetched").unwrap();
+
+        let changed = new_day(&root, 3).unwrap();
+        assert_eq!(5, changed.len());
+        let module = std::fs::read_to_string(root.join("src/day_3.rs")).unwrap();
+        assert!(module.contains("impl Solution for Day3 {\n    const DAY: u8 = 3;"));
+        assert!(module.contains("include_str!(\"input/example_day_3.txt\")"));
+        let bin = std::fs::read_to_string(root.join("src/bin/day_3.rs")).unwrap();
+        assert!(bin.contains("run_day(3, input.as_deref())"));
+        assert_eq!(
+            "fetched",
+            std::fs::read_to_string(root.join("src/input/day_3.txt")).unwrap()
+        );
+        assert!(root.join("src/input/example_day_3.txt").is_file());
+
+        assert!(new_day(&root, 3).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn existing_bin_test() {
+        let root = std::env::temp_dir().join(format!(
+            "advent_of_code_2022_{}_existing_bin_test",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/bin")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        std::fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+        std::fs::write(root.join("src/bin/day_3.rs"), "custom").unwrap();
+
+        let error = new_day(&root, 3).unwrap_err();
+        assert!(error.to_string().ends_with("day_3.rs already exists"));
+        assert_eq!(
+            "custom",
+            std::fs::read_to_string(root.join("src/bin/day_3.rs")).unwrap()
+        );
+        assert!(!root.join("src/day_3.rs").exists());
+        assert_eq!(
+            LIB,
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap()
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
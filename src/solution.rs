@@ -0,0 +1,83 @@
+use std::{any::Any, fmt::Display};
+
+use color_eyre::eyre::{eyre, Result};
+
+/// A single day's puzzle, split into parsing the input and solving each part from the parsed input
+pub trait Solution {
+    /// Day of the advent calendar this solves
+    const DAY: u8;
+
+    type Input: 'static;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne>;
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo>;
+}
+
+/// Parsed input of a [`DynSolution`], only usable with the solution that produced it
+pub type ParsedInput = Box<dyn Any>;
+
+/// Object safe view of a [`Solution`], so that all days can be driven from the same registry
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<ParsedInput>;
+    fn part_one(&self, input: &ParsedInput) -> Result<String>;
+    fn part_two(&self, input: &ParsedInput) -> Result<String>;
+
+    fn part(&self, part: u8, input: &ParsedInput) -> Result<String> {
+        match part {
+            1 => self.part_one(input),
+            2 => self.part_two(input),
+            _ => Err(eyre!("Day {} has no part {part}", self.day())),
+        }
+    }
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<ParsedInput> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part_one(&self, input: &ParsedInput) -> Result<String> {
+        S::part_one(downcast::<S>(input)?).map(|answer| answer.to_string())
+    }
+
+    fn part_two(&self, input: &ParsedInput) -> Result<String> {
+        S::part_two(downcast::<S>(input)?).map(|answer| answer.to_string())
+    }
+}
+
+fn downcast<S: Solution>(input: &ParsedInput) -> Result<&S::Input> {
+    input
+        .downcast_ref::<S::Input>()
+        .ok_or(eyre!("Input was not parsed by day {}", S::DAY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynSolution;
+    use crate::day_1::Day1;
+
+    #[test]
+    fn dyn_solution_test() {
+        let input = "1000
+2000
+
+4000
+
+";
+        let parsed = Day1.parse(input).unwrap();
+        assert_eq!("4000", Day1.part(1, &parsed).unwrap());
+        assert_eq!("7000", Day1.part(2, &parsed).unwrap());
+        assert!(Day1.part(3, &parsed).is_err());
+
+        let other_day: &dyn DynSolution = &crate::day_2::Day2;
+        assert!(other_day.part_one(&parsed).is_err());
+    }
+}